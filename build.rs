@@ -0,0 +1,29 @@
+use std::env;
+use std::fs;
+use std::path::Path;
+
+// With the `embed-inputs` feature, generate a table of `include_str!`s for
+// every day whose input exists under data/. Days without an input are left
+// out, so a fresh clone still builds.
+fn main() {
+    println!("cargo:rerun-if-changed=data");
+    println!("cargo:rerun-if-changed=build.rs");
+
+    if env::var_os("CARGO_FEATURE_EMBED_INPUTS").is_none() {
+        return;
+    }
+
+    let data = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("data");
+    let mut table = String::from("const INPUTS: &[(u32, &str)] = &[\n");
+    for day in 1..=25 {
+        let path = data.join(format!("day{}", day));
+        if path.is_file() {
+            println!("cargo:rerun-if-changed={}", path.display());
+            table.push_str(&format!("    ({}, include_str!({:?})),\n", day, path));
+        }
+    }
+    table.push_str("];\n");
+
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("inputs.rs");
+    fs::write(out, table).unwrap();
+}
@@ -0,0 +1,138 @@
+use std::fmt;
+
+/// The answer to a part. Most puzzles want a number of some size, but some
+/// want text like a comma separated list or a password.
+#[derive(Clone, Debug)]
+pub enum Answer {
+    I64(i64),
+    U64(u64),
+    I128(i128),
+    U128(u128),
+    String(String),
+}
+
+impl Answer {
+    /// Read an answer written out as text, such as an expected answer from a
+    /// file. Anything that doesn't read back as the same integer, like
+    /// `00123` or `+5`, is kept as a string.
+    pub fn parse(text: &str) -> Self {
+        let number = match (text.parse(), text.parse()) {
+            (Ok(number), _) => Answer::I128(number),
+            (_, Ok(number)) => Answer::U128(number),
+            _ => return Answer::String(text.to_string()),
+        };
+        if number.to_string() == text {
+            number
+        } else {
+            Answer::String(text.to_string())
+        }
+    }
+
+    /// A number's sign and magnitude, so numbers compare equal whatever type
+    /// they were computed in.
+    fn number(&self) -> Option<(bool, u128)> {
+        match *self {
+            Answer::I64(n) => Some((n < 0, n.unsigned_abs() as u128)),
+            Answer::U64(n) => Some((false, n as u128)),
+            Answer::I128(n) => Some((n < 0, n.unsigned_abs())),
+            Answer::U128(n) => Some((false, n)),
+            Answer::String(_) => None,
+        }
+    }
+}
+
+impl PartialEq for Answer {
+    /// Numbers are equal by value. A text answer is equal to anything written
+    /// out the same way, so a solver can give a digit code like `1985` as
+    /// text.
+    fn eq(&self, other: &Self) -> bool {
+        match (self.number(), other.number()) {
+            (Some(a), Some(b)) => a == b,
+            _ => self.to_string() == other.to_string(),
+        }
+    }
+}
+
+impl Eq for Answer {}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::I64(n) => write!(f, "{}", n),
+            Answer::U64(n) => write!(f, "{}", n),
+            Answer::I128(n) => write!(f, "{}", n),
+            Answer::U128(n) => write!(f, "{}", n),
+            Answer::String(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+macro_rules! from_integer {
+    ($variant:ident: $($t:ty),*) => {
+        $(impl From<$t> for Answer {
+            fn from(value: $t) -> Self {
+                Answer::$variant(value.into())
+            }
+        })*
+    };
+}
+
+from_integer!(I64: i32, i64);
+from_integer!(U64: u32, u64);
+from_integer!(I128: i128);
+from_integer!(U128: u128);
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Self {
+        Answer::U64(value as u64)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::String(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::String(value.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_equality() {
+        assert_eq!(Answer::from(41usize), Answer::from(41i32));
+        assert_eq!(Answer::from(-3i64), Answer::I128(-3));
+        assert_eq!(Answer::from(u128::MAX), Answer::U128(u128::MAX));
+        assert_ne!(Answer::from(-3i64), Answer::from(3u64));
+        assert_eq!(Answer::from("41"), Answer::from(41u32));
+        assert_ne!(Answer::from("041"), Answer::from(41u32));
+        assert_eq!(Answer::from("6,0,1"), Answer::from("6,0,1".to_string()));
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Answer::parse("11387"), Answer::from(11387usize));
+        assert_eq!(Answer::parse("-12"), Answer::from(-12i32));
+        assert_eq!(
+            Answer::parse("340282366920938463463374607431768211455"),
+            Answer::U128(u128::MAX)
+        );
+        assert_eq!(Answer::parse("4,6,3,5"), Answer::from("4,6,3,5"));
+        assert_eq!(Answer::parse("1985"), Answer::from("1985"));
+        assert_eq!(Answer::parse("00123").to_string(), "00123");
+        assert_ne!(Answer::parse("00123"), Answer::from(123u32));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Answer::from(-7i32).to_string(), "-7");
+        assert_eq!(Answer::from(u128::MAX).to_string(), u128::MAX.to_string());
+        assert_eq!(Answer::from("abc").to_string(), "abc");
+    }
+}
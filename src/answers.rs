@@ -0,0 +1,96 @@
+use std::collections::BTreeMap;
+use std::fs::read_to_string;
+use std::io;
+
+use crate::answer::Answer;
+use crate::config;
+
+/// Where the known answers are kept.
+pub fn path() -> String {
+    config::get().path("answers")
+}
+
+/// Expected answers keyed by (day, part).
+pub type Answers = BTreeMap<(u32, usize), Answer>;
+
+/// Load the answers file. A missing file just means nothing is known yet,
+/// but a line that can't be read is an error rather than a check that never
+/// runs.
+pub fn load(path: &str) -> io::Result<Answers> {
+    match read_to_string(path) {
+        Ok(contents) => {
+            parse(&contents).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+        }
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Answers::new()),
+        Err(err) => Err(err),
+    }
+}
+
+/// Each line holds `<day> <part> <answer>`. Blank lines and lines starting
+/// with `#` are skipped, and any other line that doesn't fit is an error
+/// naming its line number.
+pub fn parse(contents: &str) -> Result<Answers, String> {
+    let mut answers = Answers::new();
+
+    for (i, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let mut fields = line.splitn(3, char::is_whitespace);
+        let entry = match (fields.next(), fields.next(), fields.next()) {
+            (Some(day), Some(part), Some(answer)) if !answer.trim().is_empty() => day
+                .parse()
+                .ok()
+                .zip(part.parse().ok())
+                .map(|key| (key, answer.trim())),
+            _ => None,
+        };
+        let Some((key, answer)) = entry else {
+            return Err(format!(
+                "line {}: expected `<day> <part> <answer>`, got `{}`",
+                i + 1,
+                line
+            ));
+        };
+        answers.insert(key, Answer::parse(answer));
+    }
+
+    Ok(answers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let answers = parse(
+            "# day part answer
+1 1 11
+1 2 31
+
+7 2 11387
+",
+        )
+        .unwrap();
+
+        assert_eq!(answers.len(), 3);
+        assert_eq!(answers[&(1, 1)], Answer::from(11u32));
+        assert_eq!(answers[&(1, 2)], Answer::from(31u32));
+        assert_eq!(answers[&(7, 2)], Answer::from(11387usize));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse("1 1 11\n6 2\n"),
+            Err("line 2: expected `<day> <part> <answer>`, got `6 2`".to_string())
+        );
+        assert!(parse("six 2 6\n").unwrap_err().starts_with("line 1:"));
+        assert!(parse("\n# comment\nbad line\n")
+            .unwrap_err()
+            .starts_with("line 3:"));
+    }
+}
@@ -0,0 +1,190 @@
+use std::env;
+use std::fs::{create_dir_all, read_to_string, write};
+use std::path::Path;
+
+use crate::config;
+use crate::http;
+use crate::submit::{self, Outcome};
+
+pub const DEFAULT_URL: &str = "https://adventofcode.com";
+
+/// Talks to the Advent of Code website, or anything pretending to be it.
+pub struct Client {
+    base_url: String,
+    session: String,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        Client {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+        }
+    }
+
+    /// Build a client for the site at `AOC_URL` (the real one by default)
+    /// using the session token from `session()`.
+    pub fn from_env() -> Result<Self, String> {
+        let base_url = env::var("AOC_URL").unwrap_or_else(|_| DEFAULT_URL.to_string());
+        Ok(Client::new(&base_url, &session()?))
+    }
+
+    /// Download a day's puzzle input.
+    pub fn input(&self, day: u32) -> Result<String, String> {
+        let year = config::get().year;
+        let url = format!("{}/{}/day/{}/input", self.base_url, year, day);
+        let response = http::request(&url, &self.session, None)?;
+
+        match response.status {
+            200 => Ok(response.body),
+            404 => Err(format!("Day {} isn't unlocked yet.", day)),
+            status => Err(format!(
+                "Couldn't download day {} ({}): {}",
+                day,
+                status,
+                response.body.lines().next().unwrap_or_default()
+            )),
+        }
+    }
+
+    /// Submit an answer for one part of a day.
+    pub fn submit(&self, day: u32, part: usize, answer: &str) -> Result<Outcome, String> {
+        let year = config::get().year;
+        let url = format!("{}/{}/day/{}/answer", self.base_url, year, day);
+        let form = format!("level={}&answer={}", part, encode(answer));
+        let response = http::request(&url, &self.session, Some(&form))?;
+
+        match response.status {
+            200 => Ok(submit::parse_response(&response.body)),
+            status => Err(format!("Couldn't submit day {} ({})", day, status)),
+        }
+    }
+}
+
+/// Percent-encode a form value.
+fn encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+/// The session token, from `AOC_SESSION` or else the configured session
+/// file (`~/.config/aoc/session` by default).
+pub fn session() -> Result<String, String> {
+    if let Ok(session) = env::var("AOC_SESSION") {
+        return Ok(session.trim().to_string());
+    }
+
+    let path = match &config::get().session_file {
+        Some(path) => path.clone(),
+        None => match env::var_os("HOME") {
+            Some(home) => Path::new(&home).join(".config/aoc/session"),
+            None => return Err("Set AOC_SESSION to your session cookie.".to_string()),
+        },
+    };
+
+    match read_to_string(&path) {
+        Ok(session) => Ok(session.trim().to_string()),
+        Err(_) => Err(format!(
+            "Set AOC_SESSION or put your session cookie in {}.",
+            path.display()
+        )),
+    }
+}
+
+/// Download a day's input to `path` unless it's already there, returning
+/// whether anything was downloaded. The client is only created when needed,
+/// so a cached input doesn't need a session.
+pub fn fetch(
+    day: u32,
+    path: &Path,
+    client: impl FnOnce() -> Result<Client, String>,
+) -> Result<bool, String> {
+    if path.exists() {
+        return Ok(false);
+    }
+
+    let input = client()?.input(day)?;
+
+    if let Some(parent) = path.parent() {
+        create_dir_all(parent)
+            .map_err(|err| format!("Couldn't create {}: {}", parent.display(), err))?;
+    }
+    write(path, input).map_err(|err| format!("Couldn't write {}: {}", path.display(), err))?;
+
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::stub;
+    use crate::test_util::temp_dir;
+
+    #[test]
+    fn test_fetch() {
+        let server = stub::serve(200, "3   4\n4   3\n");
+        let path = temp_dir("fetch").join("data/day1");
+
+        let fetched = fetch(1, &path, || Ok(Client::new(&server.url, "abc"))).unwrap();
+        assert!(fetched);
+        assert_eq!(read_to_string(&path).unwrap(), "3   4\n4   3\n");
+
+        let requests = server.requests.lock().unwrap();
+        assert!(requests[0].starts_with("GET /2024/day/1/input "));
+        assert!(requests[0].contains("Cookie: session=abc"));
+    }
+
+    #[test]
+    fn test_fetch_cached() {
+        let server = stub::serve(200, "new");
+        let dir = temp_dir("cached");
+        create_dir_all(&dir).unwrap();
+        let path = dir.join("day1");
+        write(&path, "old").unwrap();
+
+        let fetched = fetch(1, &path, || Ok(Client::new(&server.url, "abc"))).unwrap();
+        assert!(!fetched);
+        assert_eq!(read_to_string(&path).unwrap(), "old");
+        assert!(server.requests.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_submit() {
+        let server = stub::serve(
+            200,
+            "<article><p>That's not the right answer; your answer is too low. \
+             Please wait one minute before trying again.</p></article>",
+        );
+        let client = Client::new(&server.url, "abc");
+
+        let outcome = client.submit(7, 2, "1,2 3").unwrap();
+        assert_eq!(
+            outcome,
+            Outcome::Wrong(
+                Some(submit::Hint::TooLow),
+                std::time::Duration::from_secs(60)
+            )
+        );
+
+        let requests = server.requests.lock().unwrap();
+        assert!(requests[0].starts_with("POST /2024/day/7/answer "));
+        assert!(requests[0].ends_with("level=2&answer=1%2C2%203"));
+    }
+
+    #[test]
+    fn test_fetch_locked() {
+        let server = stub::serve(404, "Not found");
+        let path = temp_dir("locked").join("day25");
+
+        let err = fetch(25, &path, || Ok(Client::new(&server.url, "abc"))).unwrap_err();
+        assert_eq!(err, "Day 25 isn't unlocked yet.");
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,214 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs::{read_to_string, write};
+use std::io;
+use std::time::Duration;
+
+use crate::config;
+use crate::solution::{Day, Selection};
+
+/// Where the benchmark medians to compare against are kept, one file per
+/// year.
+pub fn baseline_path() -> String {
+    let config = config::get();
+    config.path(&format!("baseline-{}", config.year))
+}
+
+/// Options for the `bench` subcommand.
+pub struct Options {
+    pub runs: usize,
+    /// Percentage slowdown over the baseline median that counts as a regression
+    pub threshold: f64,
+    /// Overwrite the stored baseline with this run's results
+    pub save: bool,
+    /// Compare against the stored baseline. Only timings of a day's usual
+    /// input are worth comparing.
+    pub compare: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            runs: 50,
+            threshold: 10.0,
+            save: false,
+            compare: true,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub std_dev: Duration,
+}
+
+impl Stats {
+    pub fn new(samples: &[Duration]) -> Self {
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+
+        let len = sorted.len();
+        let median = if len.is_multiple_of(2) {
+            (sorted[len / 2 - 1] + sorted[len / 2]) / 2
+        } else {
+            sorted[len / 2]
+        };
+
+        let nanos: Vec<f64> = sorted.iter().map(|d| d.as_nanos() as f64).collect();
+        let mean = nanos.iter().sum::<f64>() / len as f64;
+        let variance = nanos.iter().map(|n| (n - mean).powi(2)).sum::<f64>() / len as f64;
+
+        Stats {
+            min: sorted[0],
+            median,
+            mean: Duration::from_nanos(mean.round() as u64),
+            std_dev: Duration::from_nanos(variance.sqrt().round() as u64),
+        }
+    }
+}
+
+/// Stored medians keyed by (day, part).
+type Baseline = BTreeMap<(u32, usize), Duration>;
+
+fn load_baseline(path: &str) -> io::Result<Baseline> {
+    match read_to_string(path) {
+        Ok(contents) => Ok(parse_baseline(&contents)),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Baseline::new()),
+        Err(err) => Err(err),
+    }
+}
+
+fn parse_baseline(contents: &str) -> Baseline {
+    let mut baseline = Baseline::new();
+
+    for line in contents.lines() {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if let [day, part, nanos] = fields[..] {
+            if let (Ok(day), Ok(part), Ok(nanos)) = (day.parse(), part.parse(), nanos.parse()) {
+                baseline.insert((day, part), Duration::from_nanos(nanos));
+            }
+        }
+    }
+
+    baseline
+}
+
+fn format_baseline(baseline: &Baseline) -> String {
+    baseline
+        .iter()
+        .map(|((day, part), median)| format!("{} {} {}\n", day, part, median.as_nanos()))
+        .collect()
+}
+
+/// Benchmark the selected parts of a day, compare them against the stored
+/// baseline and return whether any part regressed.
+pub fn run(
+    day: &Day,
+    input: &str,
+    selection: Selection,
+    options: &Options,
+) -> Result<bool, Box<dyn Error>> {
+    let path = baseline_path();
+    let mut baseline = match options.compare {
+        true => load_baseline(&path).map_err(|err| format!("Couldn't read {}: {}", path, err))?,
+        false => Baseline::new(),
+    };
+    let mut regressed = false;
+    let mut changed = false;
+
+    // Warm up caches and the allocator before taking samples
+    day.sample(input, 1, selection)?;
+    let samples = day.sample(input, options.runs, selection)?;
+
+    println!("Day {} over {} runs", day.number, options.runs);
+    println!(
+        "{:>4}  {:>12}  {:>12}  {:>12}  {:>12}  {:>9}",
+        "Part", "Min", "Median", "Mean", "Std dev", "Change"
+    );
+
+    for (part, samples) in samples {
+        let stats = Stats::new(&samples);
+
+        let change = match baseline.get(&(day.number, part)) {
+            _ if !options.compare => format!("{:>9}", "-"),
+            Some(old) => {
+                let old = old.as_nanos() as f64;
+                let percent = (stats.median.as_nanos() as f64 - old) / old * 100.0;
+                let flag = if percent > options.threshold {
+                    regressed = true;
+                    "  REGRESSION"
+                } else {
+                    ""
+                };
+                format!("{:>+8.1}%{}", percent, flag)
+            }
+            None => format!("{:>9}", "new"),
+        };
+
+        println!(
+            "{:>4}  {:>12}  {:>12}  {:>12}  {:>12}  {}",
+            part,
+            format!("{:.2?}", stats.min),
+            format!("{:.2?}", stats.median),
+            format!("{:.2?}", stats.mean),
+            format!("{:.2?}", stats.std_dev),
+            change
+        );
+
+        if options.compare && (options.save || !baseline.contains_key(&(day.number, part))) {
+            baseline.insert((day.number, part), stats.median);
+            changed = true;
+        }
+    }
+
+    if changed {
+        write(&path, format_baseline(&baseline))
+            .map_err(|err| format!("Couldn't update {}: {}", path, err))?;
+        println!("Saved baseline to {}", path);
+    }
+
+    Ok(regressed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn micros(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|&v| Duration::from_micros(v)).collect()
+    }
+
+    #[test]
+    fn test_stats() {
+        let stats = Stats::new(&micros(&[4, 2, 8, 6]));
+        assert_eq!(
+            stats,
+            Stats {
+                min: Duration::from_micros(2),
+                median: Duration::from_micros(5),
+                mean: Duration::from_micros(5),
+                std_dev: Duration::from_nanos(2236),
+            }
+        );
+    }
+
+    #[test]
+    fn test_stats_odd() {
+        let stats = Stats::new(&micros(&[3, 1, 2]));
+        assert_eq!(stats.median, Duration::from_micros(2));
+    }
+
+    #[test]
+    fn test_baseline_round_trip() {
+        let mut baseline = Baseline::new();
+        baseline.insert((6, 2), Duration::from_nanos(123_456));
+        baseline.insert((1, 1), Duration::from_nanos(42));
+
+        let contents = format_baseline(&baseline);
+        assert_eq!(contents, "1 1 42\n6 2 123456\n");
+        assert_eq!(parse_baseline(&contents), baseline);
+    }
+}
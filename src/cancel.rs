@@ -0,0 +1,54 @@
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// A solver ran past its time budget and gave up.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Timeout;
+
+impl fmt::Display for Timeout {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "timed out")
+    }
+}
+
+/// Tells a solver when to give up. Solvers that can take a long time call
+/// `check` at their loop boundaries and bail out with `?` once it fails.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Token {
+    deadline: Option<Instant>,
+}
+
+impl Token {
+    /// A token that never runs out.
+    pub fn never() -> Self {
+        Token { deadline: None }
+    }
+
+    /// A token that runs out `budget` from now, or never without a budget.
+    /// A budget too big for the clock to represent never runs out either.
+    pub fn after(budget: Option<Duration>) -> Self {
+        Token {
+            deadline: budget.and_then(|budget| Instant::now().checked_add(budget)),
+        }
+    }
+
+    pub fn check(&self) -> Result<(), Timeout> {
+        match self.deadline {
+            Some(deadline) if Instant::now() >= deadline => Err(Timeout),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check() {
+        assert_eq!(Token::never().check(), Ok(()));
+        assert_eq!(Token::after(None).check(), Ok(()));
+        assert_eq!(Token::after(Some(Duration::from_secs(60))).check(), Ok(()));
+        assert_eq!(Token::after(Some(Duration::ZERO)).check(), Err(Timeout));
+    }
+}
@@ -0,0 +1,158 @@
+use std::time::Duration;
+
+use crate::config::{self, Config};
+use crate::input::Source;
+use crate::runner::Format;
+use crate::solution::Selection;
+
+/// Command line arguments with the global flags pulled out. Anything that
+/// isn't a global flag is left in `positional` for the subcommand to handle.
+#[derive(Debug, PartialEq)]
+pub struct Args {
+    pub positional: Vec<String>,
+    pub input: Source,
+    pub selection: Selection,
+    pub format: Format,
+    /// How long each part gets before it's cancelled
+    pub timeout: Option<Duration>,
+}
+
+/// Parse the arguments, falling back to `config` for flags that aren't given.
+pub fn parse(args: impl IntoIterator<Item = String>, config: &Config) -> Result<Args, String> {
+    let mut positional = vec![];
+    let mut input = Source::Default;
+    let mut selection = Selection::Both;
+    let mut format = config.format;
+    let mut timeout = config.timeout;
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--input" => {
+                input = match args.next().as_deref() {
+                    Some("-") => Source::Stdin,
+                    Some(path) => Source::Path(path.to_string()),
+                    None => return Err("--input needs a path, or - for stdin".to_string()),
+                }
+            }
+            "--part" => {
+                selection = match args.next().as_deref() {
+                    Some("1") => Selection::Only(1),
+                    Some("2") => Selection::Only(2),
+                    _ => return Err("--part needs to be 1 or 2".to_string()),
+                }
+            }
+            "--format" => {
+                format = match args.next().as_deref().and_then(Format::from_name) {
+                    Some(format) => format,
+                    None => return Err("--format needs to be text or json".to_string()),
+                }
+            }
+            "--timeout" => {
+                let seconds = args.next().and_then(|x| x.parse().ok());
+                timeout = match seconds.and_then(config::timeout) {
+                    Some(timeout) => Some(timeout),
+                    None => return Err("--timeout needs a positive number of seconds".to_string()),
+                }
+            }
+            _ => positional.push(arg),
+        }
+    }
+
+    Ok(Args {
+        positional,
+        input,
+        selection,
+        format,
+        timeout,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cancel::Token;
+
+    fn args(args: &str) -> Result<Args, String> {
+        parse(
+            args.split_whitespace().map(String::from),
+            &Config::default(),
+        )
+    }
+
+    #[test]
+    fn test_input_path() {
+        assert_eq!(
+            args("6 --input other/day6.txt"),
+            Ok(Args {
+                positional: vec!["6".to_string()],
+                input: Source::Path("other/day6.txt".to_string()),
+                selection: Selection::Both,
+                format: Format::Text,
+                timeout: None,
+            })
+        );
+    }
+
+    #[test]
+    fn test_input_stdin() {
+        assert_eq!(args("--input - bench 6").unwrap().input, Source::Stdin);
+        assert_eq!(
+            args("--input - bench 6").unwrap().positional,
+            ["bench", "6"]
+        );
+    }
+
+    #[test]
+    fn test_input_missing() {
+        assert!(args("6 --input").is_err());
+    }
+
+    #[test]
+    fn test_part() {
+        assert_eq!(args("6 --part 2").unwrap().selection, Selection::Only(2));
+        assert!(args("6 --part 3").is_err());
+        assert!(args("6 --part").is_err());
+    }
+
+    #[test]
+    fn test_format() {
+        assert_eq!(args("all --format json").unwrap().format, Format::Json);
+        assert!(args("all --format xml").is_err());
+    }
+
+    #[test]
+    fn test_timeout() {
+        assert_eq!(
+            args("7 --timeout 2.5").unwrap().timeout,
+            Some(Duration::from_millis(2500))
+        );
+        assert!(args("7 --timeout 0").is_err());
+        assert!(args("7 --timeout soon").is_err());
+        assert!(args("7 --timeout").is_err());
+        assert!(args("7 --timeout 1e30").is_err());
+
+        // Fits in a Duration but not on the clock, so it never runs out
+        let huge = args("7 --timeout 9.3e18").unwrap().timeout;
+        assert!(huge.is_some());
+        assert_eq!(Token::after(huge).check(), Ok(()));
+    }
+
+    #[test]
+    fn test_config_defaults() {
+        let config = Config {
+            format: Format::Json,
+            timeout: Some(Duration::from_secs(3)),
+            ..Config::default()
+        };
+        let parse = |args: &str| parse(args.split_whitespace().map(String::from), &config);
+
+        let defaults = parse("6").unwrap();
+        assert_eq!(defaults.format, Format::Json);
+        assert_eq!(defaults.timeout, Some(Duration::from_secs(3)));
+
+        let flags = parse("6 --format text --timeout 1").unwrap();
+        assert_eq!(flags.format, Format::Text);
+        assert_eq!(flags.timeout, Some(Duration::from_secs(1)));
+    }
+}
@@ -0,0 +1,301 @@
+use std::env;
+use std::fs::read_to_string;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use crate::runner::Format;
+
+pub const CONFIG_PATH: &str = "aoc.toml";
+
+/// Settings shared by every command. Each one comes from the first of these
+/// that sets it: a command line flag (only the format and timeout have one),
+/// an `AOC_*` environment variable, `aoc.toml`, and then the default.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Config {
+    /// Where inputs, answers, baselines and submissions are kept
+    pub data_dir: PathBuf,
+    pub year: u32,
+    pub format: Format,
+    /// How long each part gets before it's cancelled
+    pub timeout: Option<Duration>,
+    /// The file holding the session cookie, `~/.config/aoc/session` if unset
+    pub session_file: Option<PathBuf>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            data_dir: PathBuf::from("data"),
+            year: 2024,
+            format: Format::Text,
+            timeout: None,
+            session_file: None,
+        }
+    }
+}
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+/// Make `config` the one `get` hands out. Only the first call counts.
+pub fn init(config: Config) {
+    let _ = CONFIG.set(config);
+}
+
+/// The config loaded at startup, or the defaults if nothing was loaded.
+pub fn get() -> &'static Config {
+    CONFIG.get_or_init(Config::default)
+}
+
+impl Config {
+    /// Read `aoc.toml` (or the file named by `AOC_CONFIG`) if there is one,
+    /// then apply the environment on top.
+    pub fn load() -> Result<Self, String> {
+        let path = env::var("AOC_CONFIG").unwrap_or_else(|_| CONFIG_PATH.to_string());
+        let mut config = match read_to_string(&path) {
+            Ok(contents) => Config::parse(&contents).map_err(|err| format!("{} {}", path, err))?,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Config::default(),
+            Err(err) => return Err(format!("Couldn't read {}: {}", path, err)),
+        };
+
+        config.apply_env(|name| env::var(name).ok())?;
+        Ok(config)
+    }
+
+    /// Parse the config file. Only `key = value` lines and comments are
+    /// understood, which is all of TOML this needs.
+    fn parse(contents: &str) -> Result<Self, String> {
+        let mut config = Config::default();
+
+        for (i, line) in contents.lines().enumerate() {
+            let line = strip_comment(line).trim();
+            if line.is_empty() {
+                continue;
+            }
+            let error = |message: String| format!("line {}: {}", i + 1, message);
+
+            if line.starts_with('[') {
+                return Err(error("tables aren't supported".to_string()));
+            }
+            let Some((key, value)) = line.split_once('=') else {
+                return Err(error("expected `key = value`".to_string()));
+            };
+            let value = Value::parse(value.trim()).map_err(error)?;
+            config.set(key.trim(), value).map_err(error)?;
+        }
+
+        Ok(config)
+    }
+
+    /// Override settings with any `AOC_*` variables `var` knows about.
+    fn apply_env(&mut self, var: impl Fn(&str) -> Option<String>) -> Result<(), String> {
+        let settings = [
+            ("AOC_DATA_DIR", "data_dir"),
+            ("AOC_YEAR", "year"),
+            ("AOC_FORMAT", "format"),
+            ("AOC_TIMEOUT", "timeout"),
+            ("AOC_SESSION_FILE", "session_file"),
+        ];
+
+        for (name, key) in settings {
+            let Some(text) = var(name) else {
+                continue;
+            };
+            // Environment variables are always strings, so guess the type
+            let value = match text.parse::<f64>() {
+                Ok(number) if key != "data_dir" && key != "session_file" => Value::Number(number),
+                _ => Value::String(text),
+            };
+            self.set(key, value)
+                .map_err(|err| format!("{}: {}", name, err))?;
+        }
+
+        Ok(())
+    }
+
+    fn set(&mut self, key: &str, value: Value) -> Result<(), String> {
+        match (key, value) {
+            ("data_dir", Value::String(dir)) => self.data_dir = expand_home(&dir),
+            ("year", Value::Number(year)) if year.fract() == 0.0 && year >= 2015.0 => {
+                self.year = year as u32
+            }
+            ("year", _) => return Err("year needs to be 2015 or later".to_string()),
+            ("format", Value::String(name)) if Format::from_name(&name).is_some() => {
+                self.format = Format::from_name(&name).unwrap()
+            }
+            ("format", _) => return Err("format needs to be \"text\" or \"json\"".to_string()),
+            ("timeout", Value::Number(seconds)) if timeout(seconds).is_some() => {
+                self.timeout = timeout(seconds)
+            }
+            ("timeout", _) => return Err("timeout needs a positive number of seconds".to_string()),
+            ("session_file", Value::String(path)) => self.session_file = Some(expand_home(&path)),
+            ("data_dir" | "session_file", _) => {
+                return Err(format!("{} needs to be a string", key))
+            }
+            _ => return Err(format!("unknown setting `{}`", key)),
+        }
+        Ok(())
+    }
+
+    /// The path of a file under the data directory.
+    pub fn path(&self, name: &str) -> String {
+        self.data_dir.join(name).display().to_string()
+    }
+}
+
+/// A time budget in seconds, if it's a sensible one.
+pub fn timeout(seconds: f64) -> Option<Duration> {
+    match seconds > 0.0 {
+        true => Duration::try_from_secs_f64(seconds).ok(),
+        false => None,
+    }
+}
+
+/// Let paths start with `~/` like they would in a shell.
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), env::var_os("HOME")) {
+        (Some(rest), Some(home)) => Path::new(&home).join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+/// The kinds of TOML value the config uses.
+#[derive(Debug, PartialEq)]
+enum Value {
+    String(String),
+    Number(f64),
+}
+
+impl Value {
+    fn parse(text: &str) -> Result<Self, String> {
+        if let Some(rest) = text.strip_prefix('"') {
+            let mut value = String::new();
+            let mut chars = rest.chars();
+            while let Some(c) = chars.next() {
+                match c {
+                    '"' if chars.as_str().trim().is_empty() => return Ok(Value::String(value)),
+                    '"' => return Err("unexpected text after the string".to_string()),
+                    '\\' => match chars.next() {
+                        Some('"') => value.push('"'),
+                        Some('\\') => value.push('\\'),
+                        Some('n') => value.push('\n'),
+                        Some('t') => value.push('\t'),
+                        _ => return Err("unsupported escape in string".to_string()),
+                    },
+                    c => value.push(c),
+                }
+            }
+            Err("unterminated string".to_string())
+        } else if let Some(rest) = text.strip_prefix('\'') {
+            // Literal strings don't have escapes
+            match rest.strip_suffix('\'') {
+                Some(value) if !value.contains('\'') => Ok(Value::String(value.to_string())),
+                _ => Err("unterminated string".to_string()),
+            }
+        } else {
+            text.replace('_', "")
+                .parse()
+                .map(Value::Number)
+                .map_err(|_| format!("expected a string or number, found `{}`", text))
+        }
+    }
+}
+
+/// Drop a trailing `# comment`, leaving any `#` inside a string alone.
+fn strip_comment(line: &str) -> &str {
+    let mut quote = None;
+    let mut escaped = false;
+    for (i, c) in line.char_indices() {
+        match (quote, c) {
+            (Some('"'), '\\') if !escaped => {
+                escaped = true;
+                continue;
+            }
+            (Some(q), c) if c == q && !escaped => quote = None,
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '#') => return &line[..i],
+            _ => {}
+        }
+        escaped = false;
+    }
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let config = Config::parse(
+            r#"
+# Where everything lives
+data_dir = "inputs/2023"  # not data/
+year = 2_023
+format = 'json'
+timeout = 2.5
+session_file = "/run/secrets/aoc#1"
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config,
+            Config {
+                data_dir: PathBuf::from("inputs/2023"),
+                year: 2023,
+                format: Format::Json,
+                timeout: Some(Duration::from_millis(2500)),
+                session_file: Some(PathBuf::from("/run/secrets/aoc#1")),
+            }
+        );
+        assert_eq!(config.path("day6"), "inputs/2023/day6");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            Config::parse("year = 2024\ncolour = \"blue\"").unwrap_err(),
+            "line 2: unknown setting `colour`"
+        );
+        assert!(Config::parse("[aoc]\nyear = 2024").is_err());
+        assert!(Config::parse("year").is_err());
+        assert!(Config::parse("year = \"soon\"").is_err());
+        assert!(Config::parse("format = \"xml\"").is_err());
+        assert!(Config::parse("timeout = 0").is_err());
+        assert!(Config::parse("timeout = 1e30").is_err());
+        assert!(Config::parse("data_dir = \"data").is_err());
+        assert!(Config::parse("data_dir = \"data\" extra").is_err());
+    }
+
+    #[test]
+    fn test_env_overrides_file() {
+        let mut config = Config::parse("year = 2023\nformat = \"json\"\ntimeout = 5").unwrap();
+        config
+            .apply_env(|name| match name {
+                "AOC_YEAR" => Some("2022".to_string()),
+                "AOC_DATA_DIR" => Some("2022".to_string()),
+                _ => None,
+            })
+            .unwrap();
+
+        assert_eq!(config.year, 2022);
+        assert_eq!(config.data_dir, PathBuf::from("2022"));
+        assert_eq!(config.format, Format::Json);
+        assert_eq!(config.timeout, Some(Duration::from_secs(5)));
+
+        let err = config
+            .apply_env(|name| (name == "AOC_FORMAT").then(|| "xml".to_string()))
+            .unwrap_err();
+        assert!(err.starts_with("AOC_FORMAT: "));
+
+        let err = config
+            .apply_env(|name| (name == "AOC_TIMEOUT").then(|| "1e25".to_string()))
+            .unwrap_err();
+        assert_eq!(
+            err,
+            "AOC_TIMEOUT: timeout needs a positive number of seconds"
+        );
+    }
+}
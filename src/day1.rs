@@ -1,26 +1,58 @@
-use std::fs::read_to_string;
+use crate::answer::Answer;
+use crate::cancel::{Timeout, Token};
+use crate::error::ParseError;
+use crate::parser::{many, parse_line, signed, spaces};
+use crate::solution::Solution;
 
-pub fn run() {
-    let input = read_to_string("data/day1").unwrap();
-    println!("Part 1: {}", part1(input.clone()));
-    println!("Part 2: {}", part2(input.clone()));
+const DAY: u32 = 1;
+
+pub struct Day1;
+
+/// The two lists of location IDs, side by side.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Lists {
+    pub left: Vec<i32>,
+    pub right: Vec<i32>,
+}
+
+impl Solution for Day1 {
+    type Input = Lists;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input, _cancel: &Token) -> Result<Answer, Timeout> {
+        Ok(part1(input).into())
+    }
+
+    fn part2(input: &Self::Input, _cancel: &Token) -> Result<Answer, Timeout> {
+        Ok(part2(input).into())
+    }
 }
 
-fn parse_input(input: String) -> (Vec<i32>, Vec<i32>) {
+pub fn parse(input: &str) -> Result<Lists, ParseError> {
     let mut first = vec![];
     let mut second = vec![];
 
-    for line in input.lines() {
-        let nums = line.trim().split("   ").collect::<Vec<&str>>();
-        first.push(nums[0].parse::<i32>().unwrap());
-        second.push(nums[1].parse::<i32>().unwrap());
+    // Spaces around the pair are fine too
+    let pair = (many(spaces()), signed(), spaces(), signed(), many(spaces()));
+
+    for (i, line) in input.lines().enumerate() {
+        let (_, left, _, right, _) = parse_line(DAY, i, line, &pair, "expected two numbers")?;
+        first.push(left);
+        second.push(right);
     }
 
-    (first, second)
+    Ok(Lists {
+        left: first,
+        right: second,
+    })
 }
 
-fn part1(input: String) -> i32 {
-    let (mut first, mut second) = parse_input(input);
+pub fn part1(input: &Lists) -> i32 {
+    let mut first = input.left.clone();
+    let mut second = input.right.clone();
 
     first.sort();
     second.sort();
@@ -33,11 +65,14 @@ fn part1(input: String) -> i32 {
     distance
 }
 
-fn part2(input: String) -> i32 {
-    let (first, second) = parse_input(input);
+pub fn part2(input: &Lists) -> i32 {
+    let Lists {
+        left: first,
+        right: second,
+    } = input;
 
     let mut similarity: usize = 0;
-    for num in first {
+    for &num in first {
         // find the number of times the number appears in the second list
         let count = second.iter().filter(|&x| *x == num).count();
         similarity += count * num as usize;
@@ -60,11 +95,27 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1(INPUT.to_string()), 11);
+        assert_eq!(part1(&parse(INPUT).unwrap()), 11);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(INPUT.to_string()), 31);
+        assert_eq!(part2(&parse(INPUT).unwrap()), 31);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = parse("3   4\n4   x\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 5));
+
+        let err = parse(" 3   4\n 4   3 x\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 8));
+    }
+
+    #[test]
+    fn test_parse_spaces() {
+        let lists = parse("3   4 \n\t4 3\n").unwrap();
+        assert_eq!(lists.left, [3, 4]);
+        assert_eq!(lists.right, [4, 3]);
     }
 }
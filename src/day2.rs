@@ -1,19 +1,40 @@
-use std::fs::read_to_string;
+use crate::answer::Answer;
+use crate::cancel::{Timeout, Token};
+use crate::error::ParseError;
+use crate::parser::{self, many, map, separated, signed, spaces};
+use crate::solution::Solution;
 
-pub fn run() {
-    let input = read_to_string("data/day2").unwrap();
+const DAY: u32 = 2;
 
-    println!("Part 1: {}", part1(input.clone()));
-    println!("Part 2: {}", part2(input.clone()));
+pub struct Day2;
+
+/// One report of levels per line.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Reports {
+    pub reports: Vec<Vec<i32>>,
 }
 
-fn part1(input: String) -> i32 {
-    let mut safe_count = 0;
-    for line in input.lines() {
-        let nums = parse_line(line);
+impl Solution for Day2 {
+    type Input = Reports;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input, _cancel: &Token) -> Result<Answer, Timeout> {
+        Ok(part1(input).into())
+    }
+
+    fn part2(input: &Self::Input, _cancel: &Token) -> Result<Answer, Timeout> {
+        Ok(part2(input).into())
+    }
+}
 
+pub fn part1(input: &Reports) -> i32 {
+    let mut safe_count = 0;
+    for nums in &input.reports {
         // Check if the line is safe
-        if is_safe(nums) {
+        if is_safe(nums.clone()) {
             safe_count += 1;
         }
     }
@@ -21,11 +42,9 @@ fn part1(input: String) -> i32 {
     safe_count
 }
 
-fn part2(input: String) -> i32 {
+pub fn part2(input: &Reports) -> i32 {
     let mut safe_count = 0;
-    for line in input.lines() {
-        let nums = parse_line(line);
-
+    for nums in &input.reports {
         // If it's safe, increment the count and continue
         if is_safe(nums.clone()) {
             safe_count += 1;
@@ -53,10 +72,31 @@ fn part2(input: String) -> i32 {
     safe_count
 }
 
-fn parse_line(line: &str) -> Vec<i32> {
-    line.split_whitespace()
-        .map(|x| x.parse::<i32>().unwrap())
-        .collect()
+pub fn parse(input: &str) -> Result<Reports, ParseError> {
+    let reports = input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| parse_line(i, line))
+        .collect::<Result<_, _>>()?;
+    Ok(Reports { reports })
+}
+
+fn parse_line(index: usize, line: &str) -> Result<Vec<i32>, ParseError> {
+    let levels = map(
+        (
+            many(spaces()),
+            separated(signed(), spaces()),
+            many(spaces()),
+        ),
+        |(_, levels, _): (Vec<&str>, Vec<i32>, Vec<&str>)| levels,
+    );
+    parser::parse_line(
+        DAY,
+        index,
+        line,
+        &levels,
+        "expected numbers separated by spaces",
+    )
 }
 
 fn is_safe(nums: Vec<i32>) -> bool {
@@ -106,17 +146,20 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1(INPUT.to_string()), 2);
+        assert_eq!(part1(&parse(INPUT).unwrap()), 2);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(INPUT.to_string()), 4);
+        assert_eq!(part2(&parse(INPUT).unwrap()), 4);
     }
 
     #[test]
     fn test_parse_line() {
-        assert_eq!(parse_line("1 2 3 4 5"), vec![1, 2, 3, 4, 5]);
+        assert_eq!(parse_line(0, "1 2 3 4 5"), Ok(vec![1, 2, 3, 4, 5]));
+        assert_eq!(parse_line(0, "1 2 - 4").unwrap_err().column, 5);
+        assert_eq!(parse_line(0, " 7 6\t4 \t"), Ok(vec![7, 6, 4]));
+        assert_eq!(parse_line(0, " 7 x ").unwrap_err().column, 4);
     }
 
     #[test]
@@ -1,141 +1,102 @@
-use std::collections::VecDeque;
-use std::fs::read_to_string;
+use crate::answer::Answer;
+use crate::cancel::{Timeout, Token};
+use crate::error::ParseError;
+use crate::parser::{alt, literal, map, unsigned, Parser};
+use crate::solution::Solution;
 
 // Remember, we're doing this without regex, because what's the fun in that?
 // Gotta write our own parser!
-pub fn run() {
-    let input = read_to_string("data/day3").unwrap();
-    println!("Part 1: {}", part1(input.clone()));
-    println!("Part 2: {}", part2(input.clone()));
-}
-
-fn part1(input: String) -> i64 {
-    let mut state = State::new(input.chars().collect(), false);
-    state.execute();
-    state.acc as i64
-}
+pub struct Day3;
 
-fn part2(input: String) -> i64 {
-    let mut state = State::new(input.chars().collect(), true);
-    state.execute();
-    state.acc as i64
+/// The corrupted memory, as it was read.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Memory {
+    pub text: String,
 }
 
-struct State {
-    memory: VecDeque<char>,
-    allow_disabled: bool,
-    enabled: bool,
-    acc: i32,
-}
+impl Solution for Day3 {
+    type Input = Memory;
 
-impl State {
-    fn new(memory: VecDeque<char>, allow_disabled: bool) -> Self {
-        Self {
-            memory,
-            allow_disabled,
-            enabled: true,
-            acc: 0,
-        }
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
     }
 
-    fn execute(&mut self) {
-        loop {
-            if self.memory.is_empty() {
-                break;
-            }
-
-            match self.memory.front() {
-                Some(&'d') => {
-                    if let Some(((), remain)) = self.parse_static("do()") {
-                        self.state(true);
-                        self.memory = remain;
-                        continue;
-                    } else if let Some(((), remain)) = self.parse_static("don't()") {
-                        self.state(false);
-                        self.memory = remain;
-                        continue;
-                    }
-                }
-                Some(&'m') => {
-                    if let Some(((x, y), remain)) = self.parse_mul() {
-                        if self.enabled {
-                            self.acc += x * y;
-                        }
-                        self.memory = remain;
-                        continue;
-                    }
-                }
-                _ => {}
-            }
-
-            self.memory.pop_front(); // Drop the first character
-        }
+    fn part1(input: &Self::Input, _cancel: &Token) -> Result<Answer, Timeout> {
+        Ok(part1(input).into())
     }
 
-    fn state(&mut self, state: bool) {
-        if self.allow_disabled {
-            self.enabled = state;
-        }
+    fn part2(input: &Self::Input, _cancel: &Token) -> Result<Answer, Timeout> {
+        Ok(part2(input).into())
     }
+}
 
-    fn parse_static(&self, prefix: &str) -> Option<((), VecDeque<char>)> {
-        let mut memory = self.memory.clone();
-        if memory.len() >= prefix.len()
-            && memory.iter().take(prefix.len()).collect::<String>() == prefix
-        {
-            for _ in 0..prefix.len() {
-                memory.pop_front();
-            }
-            Some(((), memory))
-        } else {
-            None
-        }
-    }
+pub fn parse(input: &str) -> Result<Memory, ParseError> {
+    Ok(Memory {
+        text: input.to_string(),
+    })
+}
 
-    fn parse_mul(&self) -> Option<((i32, i32), VecDeque<char>)> {
-        let mut memory = self.memory.clone();
-        let prefix = "mul(";
-        if memory.len() < prefix.len()
-            || memory.iter().take(prefix.len()).collect::<String>() != prefix
-        {
-            return None;
-        }
+pub fn part1(memory: &Memory) -> i64 {
+    execute(&memory.text, false)
+}
 
-        for _ in 0..prefix.len() {
-            memory.pop_front(); // Remove "mul("
-        }
+pub fn part2(memory: &Memory) -> i64 {
+    execute(&memory.text, true)
+}
 
-        let mut num_buf = String::new();
-        while let Some(c) = memory.front() {
-            if *c == ',' {
-                memory.pop_front(); // Remove ','
-                break;
-            } else if c.is_ascii_digit() {
-                num_buf.push(*c);
-                memory.pop_front();
-            } else {
-                return None; // Invalid character for number parsing
-            }
-        }
+/// The instructions that survived the corruption.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Instruction {
+    Do,
+    Dont,
+    Mul(i64, i64),
+}
 
-        let x: i32 = num_buf.parse().ok()?;
-        num_buf.clear();
-
-        while let Some(c) = memory.front() {
-            if *c == ')' {
-                memory.pop_front(); // Remove ')'
-                break;
-            } else if c.is_ascii_digit() || *c == '-' {
-                num_buf.push(*c);
-                memory.pop_front();
-            } else {
-                return None; // Invalid character for number parsing
+/// Add up the products of every `mul` in `memory`, skipping the disabled
+/// ones if `allow_disabled` is set.
+fn execute(memory: &str, allow_disabled: bool) -> i64 {
+    // The second number can be negative, but not the first
+    let negative = map((literal("-"), unsigned()), |(_, y): (&str, i32)| -y);
+    let instruction = alt((
+        map(literal("do()"), |_| Instruction::Do),
+        map(literal("don't()"), |_| Instruction::Dont),
+        map(
+            (
+                literal("mul("),
+                unsigned(),
+                literal(","),
+                alt((unsigned(), negative)),
+                literal(")"),
+            ),
+            |(_, x, _, y, _): (&str, i32, &str, i32, &str)| Instruction::Mul(x.into(), y.into()),
+        ),
+    ));
+
+    let mut rest = memory;
+    let mut enabled = true;
+    let mut acc = 0;
+
+    while !rest.is_empty() {
+        match instruction.parse(rest) {
+            Ok((found, remain)) => {
+                match found {
+                    Instruction::Do => enabled = true,
+                    Instruction::Dont => enabled = !allow_disabled,
+                    Instruction::Mul(x, y) if enabled => acc += x * y,
+                    Instruction::Mul(..) => {}
+                }
+                rest = remain;
+            }
+            Err(_) => {
+                // Drop the first character
+                let mut chars = rest.chars();
+                chars.next();
+                rest = chars.as_str();
             }
         }
-
-        let y: i32 = num_buf.parse().ok()?;
-        Some(((x, y), memory))
     }
+
+    acc
 }
 
 #[cfg(test)]
@@ -149,19 +110,21 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        let initial_memory: VecDeque<char> = FIRST_INPUT.chars().collect();
-        let mut state = State::new(initial_memory, false);
-        state.execute();
-        //assert_eq!(state.enabled, true);
-        assert_eq!(state.acc, 161);
+        assert_eq!(part1(&parse(FIRST_INPUT).unwrap()), 161);
     }
 
     #[test]
     fn test_part2() {
-        let initial_memory: VecDeque<char> = SECOND_INPUT.chars().collect();
-        let mut state = State::new(initial_memory, true);
-        state.execute();
-        //assert_eq!(state.enabled, true);
-        assert_eq!(state.acc, 48);
+        assert_eq!(part2(&parse(SECOND_INPUT).unwrap()), 48);
+    }
+
+    #[test]
+    fn test_malformed_mul() {
+        assert_eq!(part1(&parse("mul(1234,5)mul(2,-3)").unwrap()), 6164);
+        assert_eq!(
+            part1(&parse("mul(-2,3)mul(2,+3)mul(2,3-4)mul(2,3").unwrap()),
+            0
+        );
+        assert_eq!(part1(&parse("mulmul(2,3)mul(4,5))").unwrap()), 26);
     }
 }
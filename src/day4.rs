@@ -1,225 +1,90 @@
-pub fn run() {
-    let input = include_str!("../data/day4");
+use crate::answer::Answer;
+use crate::cancel::{Timeout, Token};
+use crate::error::ParseError;
+use crate::grid::Grid;
+use crate::point::Dir8;
+use crate::solution::Solution;
 
-    println!("Part 1: {}", part1(input));
-    println!("Part 2: {}", part2(input));
-}
+const DAY: u32 = 4;
 
-fn part1(input: &str) -> usize {
-    let grid = parse_input(input);
-
-    let mut count = 0;
-
-    let x_len = grid[0].len();
-    for y in 0..grid.len() {
-        for x in 0..x_len {
-            if grid[y][x] == 'X' {
-                // Check all directions individually
-                if check_up(&grid, x, y) {
-                    count += 1;
-                }
-                if check_down(&grid, x, y) {
-                    count += 1;
-                }
-                if check_left(&grid, x, y) {
-                    count += 1;
-                }
-                if check_right(&grid, x, y) {
-                    count += 1;
-                }
-                if check_up_left(&grid, x, y) {
-                    count += 1;
-                }
-                if check_up_right(&grid, x, y) {
-                    count += 1;
-                }
-                if check_down_left(&grid, x, y) {
-                    count += 1;
-                }
-                if check_down_right(&grid, x, y) {
-                    count += 1;
-                }
-            }
-        }
-    }
+pub struct Day4;
 
-    count
+/// The word search, as a grid of letters.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WordSearch {
+    pub grid: Grid<char>,
 }
 
-fn part2(input: &str) -> usize {
-    let grid = parse_input(input);
-    let mut count = 0;
+impl Solution for Day4 {
+    type Input = WordSearch;
 
-    let x_len = grid[0].len();
-    for y in 0..grid.len() {
-        for x in 0..x_len {
-            if grid[y][x] == 'A' && tl_br(&grid, x, y) && tr_bl(&grid, x, y) {
-                count += 1;
-            }
-        }
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
     }
 
-    count
-}
-
-fn parse_input(input: &str) -> Vec<Vec<char>> {
-    input.lines().map(|line| line.chars().collect()).collect()
-}
-
-fn check_up(grid: &[Vec<char>], x: usize, y: usize) -> bool {
-    if y < 3 {
-        return false; // XMAS is 4 characters long
+    fn part1(input: &Self::Input, _cancel: &Token) -> Result<Answer, Timeout> {
+        Ok(part1(input).into())
     }
 
-    for i in 1..=3 {
-        match (i, grid[y - i][x]) {
-            (1, 'M') | (2, 'A') => {} // All is well
-            (3, 'S') => return true,  // Found XMAS
-            _ => return false,        // Invalid character
-        }
+    fn part2(input: &Self::Input, _cancel: &Token) -> Result<Answer, Timeout> {
+        Ok(part2(input).into())
     }
-
-    false
 }
 
-fn check_down(grid: &[Vec<char>], x: usize, y: usize) -> bool {
-    if y + 3 >= grid.len() {
-        return false; // XMAS is 4 characters long
-    }
-
-    for i in 1..=3 {
-        match (i, grid[y + i][x]) {
-            (1, 'M') | (2, 'A') => {} // All is well
-            (3, 'S') => return true,  // Found XMAS
-            _ => return false,        // Invalid character
-        }
-    }
-
-    false
+pub fn part1(input: &WordSearch) -> usize {
+    let grid = &input.grid;
+
+    grid.iter()
+        .filter(|&(_, &c)| c == 'X')
+        .map(|(position, _)| {
+            // Check all directions individually
+            Dir8::ALL
+                .into_iter()
+                .filter(|&direction| xmas(grid, position, direction))
+                .count()
+        })
+        .sum()
 }
 
-fn check_left(grid: &[Vec<char>], x: usize, y: usize) -> bool {
-    if x < 3 {
-        return false; // XMAS is 4 characters long
-    }
+pub fn part2(input: &WordSearch) -> usize {
+    let grid = &input.grid;
 
-    for i in 1..=3 {
-        match (i, grid[y][x - i]) {
-            (1, 'M') | (2, 'A') => {} // All is well
-            (3, 'S') => return true,  // Found XMAS
-            _ => return false,        // Invalid character
-        }
-    }
-
-    false
+    grid.iter()
+        .filter(|&(position, &c)| {
+            c == 'A' && mas(grid, position, Dir8::UpLeft) && mas(grid, position, Dir8::UpRight)
+        })
+        .count()
 }
 
-fn check_right(grid: &[Vec<char>], x: usize, y: usize) -> bool {
-    if x + 3 >= grid[y].len() {
-        return false; // XMAS is 4 characters long
-    }
-
-    for i in 1..=3 {
-        match (i, grid[y][x + i]) {
-            (1, 'M') | (2, 'A') => {} // All is well
-            (3, 'S') => return true,  // Found XMAS
-            _ => return false,        // Invalid character
-        }
-    }
-
-    false
+pub fn parse(input: &str) -> Result<WordSearch, ParseError> {
+    let grid = Grid::parse(DAY, input, |_, c| Ok(c))?;
+    Ok(WordSearch { grid })
 }
 
-fn check_up_left(grid: &[Vec<char>], x: usize, y: usize) -> bool {
-    if x < 3 || y < 3 {
-        return false; // XMAS is 4 characters long
-    }
-
-    for i in 1..=3 {
-        match (i, grid[y - i][x - i]) {
-            (1, 'M') | (2, 'A') => {} // All is well
-            (3, 'S') => return true,  // Found XMAS
-            _ => return false,        // Invalid character
+/// Whether "MAS" follows the X at `position`, reading towards `direction`.
+fn xmas(grid: &Grid<char>, mut position: (usize, usize), direction: Dir8) -> bool {
+    for letter in ['M', 'A', 'S'] {
+        match grid.offset(position, direction) {
+            Some(next) if grid[next] == letter => position = next,
+            _ => return false, // Off the edge or an invalid character
         }
     }
 
-    false
+    true
 }
 
-fn check_up_right(grid: &[Vec<char>], x: usize, y: usize) -> bool {
-    if x + 3 >= grid[y].len() || y < 3 {
-        return false; // XMAS is 4 characters long
-    }
+/// Whether the A at `position` sits between an M and an S along the line
+/// through `direction`, in either order.
+fn mas(grid: &Grid<char>, position: (usize, usize), direction: Dir8) -> bool {
+    let ends = (
+        grid.offset(position, direction),
+        grid.offset(position, direction.turn_around()),
+    );
 
-    for i in 1..=3 {
-        match (i, grid[y - i][x + i]) {
-            (1, 'M') | (2, 'A') => {} // All is well
-            (3, 'S') => return true,  // Found XMAS
-            _ => return false,        // Invalid character
-        }
+    match ends {
+        (Some(a), Some(b)) => matches!((grid[a], grid[b]), ('M', 'S') | ('S', 'M')),
+        _ => false,
     }
-
-    false
-}
-
-fn check_down_left(grid: &[Vec<char>], x: usize, y: usize) -> bool {
-    if x < 3 || y + 3 >= grid.len() {
-        return false; // XMAS is 4 characters long
-    }
-
-    for i in 1..=3 {
-        match (i, grid[y + i][x - i]) {
-            (1, 'M') | (2, 'A') => {} // All is well
-            (3, 'S') => return true,  // Found XMAS
-            _ => return false,        // Invalid character
-        }
-    }
-
-    false
-}
-
-fn check_down_right(grid: &[Vec<char>], x: usize, y: usize) -> bool {
-    if x + 3 >= grid[y].len() || y + 3 >= grid.len() {
-        return false; // XMAS is 4 characters long
-    }
-
-    for i in 1..=3 {
-        match (i, grid[y + i][x + i]) {
-            (1, 'M') | (2, 'A') => {} // All is well
-            (3, 'S') => return true,  // Found XMAS
-            _ => return false,        // Invalid character
-        }
-    }
-
-    false
-}
-
-fn tl_br(grid: &[Vec<char>], x: usize, y: usize) -> bool {
-    if x == 0 || y == 0 || x + 1 >= grid[y].len() || y + 1 >= grid.len() {
-        return false;
-    }
-
-    if (grid[y - 1][x - 1] == 'M' && grid[y + 1][x + 1] == 'S')
-        || (grid[y - 1][x - 1] == 'S' && grid[y + 1][x + 1] == 'M')
-    {
-        return true;
-    }
-
-    false
-}
-
-fn tr_bl(grid: &[Vec<char>], x: usize, y: usize) -> bool {
-    if x == 0 || y == 0 || x + 1 >= grid[y].len() || y + 1 >= grid.len() {
-        return false;
-    }
-
-    if (grid[y - 1][x + 1] == 'M' && grid[y + 1][x - 1] == 'S')
-        || (grid[y - 1][x + 1] == 'S' && grid[y + 1][x - 1] == 'M')
-    {
-        return true;
-    }
-
-    false
 }
 
 #[cfg(test)]
@@ -240,11 +105,11 @@ MXMXAXMASX
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1(INPUT), 18);
+        assert_eq!(part1(&parse(INPUT).unwrap()), 18);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(INPUT), 9);
+        assert_eq!(part2(&parse(INPUT).unwrap()), 9);
     }
 }
@@ -1,179 +1,103 @@
-pub fn run() {
-    let input = include_str!("../data/day5");
+use std::collections::HashMap;
 
-    println!("Part 1: {}", part1(input));
-    println!("Part 2: {}", part2(input));
-}
+use crate::answer::Answer;
+use crate::cancel::{Timeout, Token};
+use crate::error::ParseError;
+use crate::graph::{Cycle, Graph, NodeIndex};
+use crate::parser::{literal, parse_line, separated, unsigned};
+use crate::solution::Solution;
 
-fn part1(input: &str) -> usize {
-    let (rules, updates) = parse(input);
+const DAY: u32 = 5;
 
-    let mut total = 0;
+pub struct Day5;
 
-    for update in updates {
-        let sorted_update = sort_by_rules(update.clone(), rules.clone());
-        if sorted_update == update {
-            total += update[(update.len()) / 2] // It's correct, get the middle
-        }
-    }
+/// Ordering rules as (before, after) page pairs.
+pub type Rules = Vec<(usize, usize)>;
 
-    total
+/// The page ordering rules and the updates to check against them.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PrintQueue {
+    pub rules: Rules,
+    pub updates: Vec<Vec<usize>>,
 }
 
-fn part2(input: &str) -> usize {
-    let (rules, updates) = parse(input);
-
-    let mut total = 0;
+impl Solution for Day5 {
+    type Input = PrintQueue;
 
-    for update in updates {
-        let sorted_update = sort_by_rules(update.clone(), rules.clone());
-        if sorted_update != update {
-            total += sorted_update[(sorted_update.len()) / 2];
-        }
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
     }
 
-    total
-}
-
-pub struct Graph {
-    nodes: Vec<NodeData>,
-    edges: Vec<EdgeData>,
-}
-
-pub type NodeIndex = usize;
-
-pub struct NodeData {
-    first_outgoing_edge: Option<EdgeIndex>,
-}
-
-pub type EdgeIndex = usize;
-
-pub struct EdgeData {
-    target: NodeIndex,
-    next_outgoing_edge: Option<EdgeIndex>,
-}
-
-impl Graph {
-    pub fn add_node(&mut self) -> NodeIndex {
-        let index = self.nodes.len();
-        self.nodes.push(NodeData {
-            first_outgoing_edge: None,
-        });
-        index
+    // `parse` has already checked that every update can be sorted
+    fn part1(input: &Self::Input, _cancel: &Token) -> Result<Answer, Timeout> {
+        Ok(part1(input).expect("parse checks the rules").into())
     }
 
-    pub fn add_edge(&mut self, source: NodeIndex, target: NodeIndex) {
-        let edge_index = self.edges.len();
-        let node_data = &mut self.nodes[source];
-        self.edges.push(EdgeData {
-            target,
-            next_outgoing_edge: node_data.first_outgoing_edge,
-        });
-        node_data.first_outgoing_edge = Some(edge_index);
+    fn part2(input: &Self::Input, _cancel: &Token) -> Result<Answer, Timeout> {
+        Ok(part2(input).expect("parse checks the rules").into())
     }
-
-    pub fn successors(&self, source: NodeIndex) -> Successors {
-        let first_outgoing_edge = self.nodes[source].first_outgoing_edge;
-        Successors {
-            graph: self,
-            current_edge_index: first_outgoing_edge,
-        }
-    }
-}
-
-pub struct Successors<'graph> {
-    graph: &'graph Graph,
-    current_edge_index: Option<EdgeIndex>,
 }
 
-impl<'graph> Iterator for Successors<'graph> {
-    type Item = NodeIndex;
+pub fn part1(input: &PrintQueue) -> Result<usize, Cycle> {
+    let mut total = 0;
 
-    fn next(&mut self) -> Option<NodeIndex> {
-        match self.current_edge_index {
-            None => None,
-            Some(edge_num) => {
-                let edge = &self.graph.edges[edge_num];
-                self.current_edge_index = edge.next_outgoing_edge;
-                Some(edge.target)
-            }
+    for update in &input.updates {
+        let sorted_update = sort_by_rules(update, &input.rules)?;
+        if &sorted_update == update {
+            total += update[(update.len()) / 2] // It's correct, get the middle
         }
     }
-}
 
-fn sort_by_rules(update: Vec<usize>, rules: Vec<(usize, usize)>) -> Vec<usize> {
-    let mut graph = Graph {
-        nodes: Vec::new(),
-        edges: Vec::new(),
-    };
+    Ok(total)
+}
 
-    // Map from node value to its index in the graph
-    let mut node_indices = std::collections::HashMap::new();
+pub fn part2(input: &PrintQueue) -> Result<usize, Cycle> {
+    let mut total = 0;
 
-    // Add nodes and edges to the graph
-    for (smaller, larger) in rules {
-        if update.contains(&smaller) && update.contains(&larger) {
-            let smaller_idx = *node_indices
-                .entry(smaller)
-                .or_insert_with(|| graph.add_node());
-            let larger_idx = *node_indices
-                .entry(larger)
-                .or_insert_with(|| graph.add_node());
-            graph.add_edge(smaller_idx, larger_idx);
+    for update in &input.updates {
+        let sorted_update = sort_by_rules(update, &input.rules)?;
+        if &sorted_update != update {
+            total += sorted_update[(sorted_update.len()) / 2];
         }
     }
 
-    // Track in-degrees of all nodes
-    let mut in_degree = vec![0; graph.nodes.len()];
-    for node_idx in 0..graph.nodes.len() {
-        for successor in graph.successors(node_idx) {
-            in_degree[successor] += 1;
-        }
-    }
+    Ok(total)
+}
+
+/// Order the pages of an update so every rule between two of its pages is
+/// followed.
+///
+/// If the rules for the update contradict each other, the error holds the
+/// positions in `update` of the pages they go round in a loop through.
+pub fn sort_by_rules(update: &[usize], rules: &[(usize, usize)]) -> Result<Vec<usize>, Cycle> {
+    let mut graph = Graph::new();
 
-    // Find all nodes with no prerequisites (in-degree = 0)
-    let mut queue: Vec<usize> = in_degree
+    // Map from page number to its node in the graph
+    let nodes: HashMap<usize, NodeIndex> = update
         .iter()
-        .enumerate()
-        .filter(|&(_, &degree)| degree == 0)
-        .map(|(idx, _)| idx)
+        .map(|&page| (page, graph.add_node(page)))
         .collect();
 
-    queue.sort_unstable();
-
-    let mut sorted = Vec::new();
-
-    while let Some(node_idx) = queue.pop() {
-        sorted.push(node_idx);
-
-        for successor in graph.successors(node_idx) {
-            in_degree[successor] -= 1;
-            if in_degree[successor] == 0 {
-                queue.push(successor);
-                queue.sort_unstable(); // Keep sorted for deterministic output
-            }
+    for (smaller, larger) in rules {
+        if let (Some(&before), Some(&after)) = (nodes.get(smaller), nodes.get(larger)) {
+            graph.add_edge(before, after, ());
         }
     }
 
-    // Convert sorted indices back to original node values
-    let mut index_to_value: Vec<_> = vec![0; graph.nodes.len()];
-    for (value, &idx) in &node_indices {
-        index_to_value[idx] = *value;
-    }
-
-    sorted
-        .into_iter()
-        .map(|idx| index_to_value[idx])
-        .filter(|x| update.contains(x))
-        .collect()
+    // Nodes are added in update order, so a cycle's nodes are positions in it
+    let sorted = graph.topological_sort()?;
+    Ok(sorted.into_iter().map(|node| *graph.node(node)).collect())
 }
 
-fn parse(input: &str) -> (Vec<(usize, usize)>, Vec<Vec<usize>>) {
+pub fn parse(input: &str) -> Result<PrintQueue, ParseError> {
     let mut rules = vec![];
     let mut updates = vec![];
 
+    let rule = (unsigned(), literal("|"), unsigned());
+    let update = separated(unsigned(), literal(","));
+
     let mut first = true;
-    for line in input.lines() {
+    for (i, line) in input.lines().enumerate() {
         if line.is_empty() {
             first = false;
             continue;
@@ -181,17 +105,26 @@ fn parse(input: &str) -> (Vec<(usize, usize)>, Vec<Vec<usize>>) {
 
         match first {
             true => {
-                let split: Vec<usize> = line.split("|").map(|x| x.parse().unwrap()).collect();
-                rules.push((split[0], split[1]));
+                let (before, _, after) =
+                    parse_line(DAY, i, line, &rule, "expected a rule like 47|53")?;
+                rules.push((before, after));
             }
             false => {
-                let split: Vec<usize> = line.split(",").map(|x| x.parse().unwrap()).collect();
-                updates.push(split);
+                let message = "expected page numbers like 75,47,61";
+                let pages = parse_line(DAY, i, line, &update, message)?;
+                if let Err(Cycle(cycle)) = sort_by_rules(&pages, &rules) {
+                    let mut message = "the rules for these pages go round in a loop:".to_string();
+                    for &position in cycle.iter().chain(cycle.first()) {
+                        message += &format!(" {}", pages[position]);
+                    }
+                    return Err(ParseError::new(DAY, i, line, 0, message));
+                }
+                updates.push(pages);
             }
         }
     }
 
-    (rules, updates)
+    Ok(PrintQueue { rules, updates })
 }
 
 #[cfg(test)]
@@ -230,20 +163,52 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1(INPUT), 143);
+        assert_eq!(part1(&parse(INPUT).unwrap()), Ok(143));
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(INPUT), 123);
+        assert_eq!(part2(&parse(INPUT).unwrap()), Ok(123));
+    }
+
+    #[test]
+    fn test_rule_cycle() {
+        let rules = [(2, 3), (3, 4), (4, 2)];
+        assert_eq!(
+            sort_by_rules(&[1, 2, 3, 4], &rules),
+            Err(Cycle(vec![1, 2, 3]))
+        );
+
+        let queue = PrintQueue {
+            rules: rules.to_vec(),
+            updates: vec![vec![4, 3, 2]],
+        };
+        assert_eq!(part1(&queue), Err(Cycle(vec![0, 2, 1])));
+
+        let err = parse("2|3\n3|4\n4|2\n\n1,2\n1,2,3,4\n").unwrap_err();
+        assert_eq!((err.line, err.column), (6, 1));
+        assert!(
+            err.to_string().contains("go round in a loop: 2 3 4 2"),
+            "{}",
+            err
+        );
     }
 
     #[test]
     fn test_rule_sort() {
-        let (rules, updates) = parse(INPUT);
+        let queue = parse(INPUT).unwrap();
+        assert_eq!(
+            sort_by_rules(&queue.updates[1], &queue.rules),
+            Ok(vec![97, 61, 53, 29, 13])
+        )
+    }
+
+    #[test]
+    fn test_rule_sort_reorders() {
+        let queue = parse(INPUT).unwrap();
         assert_eq!(
-            sort_by_rules(updates[1].clone(), rules),
-            vec![97, 61, 53, 29, 13]
+            sort_by_rules(&queue.updates[3], &queue.rules),
+            Ok(vec![97, 75, 47, 61, 53])
         )
     }
 }
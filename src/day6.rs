@@ -1,25 +1,58 @@
 use std::collections::HashSet;
 
-pub fn run() {
-    let input = include_str!("../data/day6");
-    let one = part1(input);
-    println!("Part 1: {}", one);
-    println!("Part 2: {}", part2(input));
+use crate::answer::Answer;
+use crate::cancel::{Timeout, Token};
+use crate::error::ParseError;
+use crate::grid::Grid;
+use crate::point::{Dir4, Point};
+use crate::solution::Solution;
+
+const DAY: u32 = 6;
+
+pub struct Day6;
+
+/// The lab map and the guard patrolling it.
+#[derive(Clone, Debug)]
+pub struct Lab {
+    pub grid: Grid<Cell>,
+    pub guard: Guard,
 }
 
-fn part1(input: &str) -> usize {
-    let (mut grid, mut guard) = parse(input);
+impl Solution for Day6 {
+    type Input = Lab;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input, cancel: &Token) -> Result<Answer, Timeout> {
+        part1(input, cancel).map(Answer::from)
+    }
+
+    fn part2(input: &Self::Input, cancel: &Token) -> Result<Answer, Timeout> {
+        part2(input, cancel).map(Answer::from)
+    }
+}
+
+pub fn part1(input: &Lab, cancel: &Token) -> Result<usize, Timeout> {
+    let Lab {
+        mut grid,
+        mut guard,
+    } = input.clone();
 
     let mut total = 1; // One for the starting location
 
     loop {
+        // A guard walking in a circle never leaves the map
+        cancel.check()?;
+
         match guard.get_front(&grid) {
-            Some(Point::Obstacle) => guard.turn(),
-            Some(Point::Visited) => guard.forward(),
-            Some(Point::Empty) => {
+            Some(Cell::Obstacle) => guard.turn(),
+            Some(Cell::Visited) => guard.forward(),
+            Some(Cell::Empty) => {
                 guard.forward();
                 total += 1;
-                grid[guard.y][guard.x] = Point::Visited;
+                grid[guard.index()] = Cell::Visited;
             }
             None => {
                 break;
@@ -27,20 +60,26 @@ fn part1(input: &str) -> usize {
         }
     }
 
-    total
+    Ok(total)
 }
 
-fn part2(input: &str) -> usize {
-    let (mut grid, mut guard) = parse(input);
-    let start = (guard.x, guard.y);
+pub fn part2(input: &Lab, cancel: &Token) -> Result<usize, Timeout> {
+    let Lab {
+        mut grid,
+        mut guard,
+    } = input.clone();
+    let start = guard.position;
 
     let mut blocks = HashSet::new();
 
     loop {
+        // Every step walks a whole new path looking for a loop
+        cancel.check()?;
+
         match guard.get_front(&grid) {
-            Some(Point::Obstacle) => guard.turn(),
-            Some(Point::Visited) => guard.forward(),
-            Some(Point::Empty) => {
+            Some(Cell::Obstacle) => guard.turn(),
+            Some(Cell::Visited) => guard.forward(),
+            Some(Cell::Empty) => {
                 guard.forward();
             }
             None => {
@@ -48,142 +87,90 @@ fn part2(input: &str) -> usize {
             }
         }
 
-        if guard.get_front(&grid).is_some() {
-            let (x, y) = guard.front_index();
-            let previous = grid[y][x];
-            grid[y][x] = Point::Obstacle;
+        if let Some(front) = guard.front_index(&grid) {
+            let previous = grid[front];
+            grid[front] = Cell::Obstacle;
 
-            if guard.check_loop(&mut grid) {
-                blocks.insert((x, y));
+            if guard.check_loop(&grid) {
+                blocks.insert(guard.front());
             }
 
-            grid[y][x] = previous;
+            grid[front] = previous;
         }
     }
 
     blocks.remove(&start);
-    blocks.len()
+    Ok(blocks.len())
 }
 
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
-enum Point {
+pub enum Cell {
     Empty,
     Visited,
     Obstacle,
 }
 
-#[derive(PartialEq, Eq, Debug, Copy, Clone)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-struct Guard {
-    facing: Direction,
-    x: usize,
-    y: usize,
-    turns: Vec<(Direction, usize, usize)>,
-    path: Vec<(usize, usize)>, // Track the path
+#[derive(Clone, Debug)]
+pub struct Guard {
+    facing: Dir4,
+    position: Point,
+    turns: Vec<(Dir4, Point)>,
+    path: Vec<Point>, // Track the path
 }
 
 impl Guard {
-    fn new(facing: Direction, x: usize, y: usize) -> Self {
+    fn new(facing: Dir4, position: Point) -> Self {
         Guard {
             facing,
-            x,
-            y,
+            position,
             turns: Vec::new(),
-            path: vec![(x, y)], // Initialize with starting position
+            path: vec![position], // Initialize with starting position
         }
     }
 
-    fn front_index(&self) -> (usize, usize) {
-        use Direction::*;
-        match self.facing {
-            Up => (self.x, self.y - 1),
-            Down => (self.x, self.y + 1),
-            Left => (self.x - 1, self.y),
-            Right => (self.x + 1, self.y),
-        }
+    /// Where the guard is on the map. The guard never leaves it.
+    fn index(&self) -> (usize, usize) {
+        self.position.to_index().expect("the guard left the map")
     }
 
-    fn get_front(&self, grid: &[Vec<Point>]) -> Option<Point> {
-        use Direction::*;
-        match self.facing {
-            Up => {
-                if self.y != 0 {
-                    let (x, y) = self.front_index();
-                    Some(grid[y][x])
-                } else {
-                    None
-                }
-            }
-            Down => {
-                if self.y < grid.len() - 1 {
-                    let (x, y) = self.front_index();
-                    Some(grid[y][x])
-                } else {
-                    None
-                }
-            }
-            Left => {
-                if self.x != 0 {
-                    let (x, y) = self.front_index();
-                    Some(grid[y][x])
-                } else {
-                    None
-                }
-            }
-            Right => {
-                if self.x < grid[self.y].len() - 1 {
-                    let (x, y) = self.front_index();
-                    Some(grid[y][x])
-                } else {
-                    None
-                }
-            }
-        }
+    fn front(&self) -> Point {
+        self.position.step(self.facing, 1)
+    }
+
+    fn front_index(&self, grid: &Grid<Cell>) -> Option<(usize, usize)> {
+        grid.index_of(self.front())
+    }
+
+    fn get_front(&self, grid: &Grid<Cell>) -> Option<Cell> {
+        self.front_index(grid).map(|front| grid[front])
     }
 
     fn turn(&mut self) {
-        use Direction::*;
-        self.turns.push((self.facing, self.x, self.y));
-        match self.facing {
-            Up => self.facing = Right,
-            Right => self.facing = Down,
-            Down => self.facing = Left,
-            Left => self.facing = Up,
-        };
+        self.turns.push((self.facing, self.position));
+        self.facing = self.facing.turn_right();
     }
 
+    /// Take a step. Only call this once `get_front` has found a cell there.
     fn forward(&mut self) {
-        use Direction::*;
-        match self.facing {
-            Up => self.y -= 1,
-            Down => self.y += 1,
-            Left => self.x -= 1,
-            Right => self.x += 1,
-        }
+        self.position = self.front();
 
-        self.path.push((self.x, self.y)); // Record the move
+        self.path.push(self.position); // Record the move
     }
 
-    fn check_loop(&mut self, grid: &mut [Vec<Point>]) -> bool {
+    fn check_loop(&mut self, grid: &Grid<Cell>) -> bool {
         // Check for loops
-        let mut new_guard = Guard::new(self.facing, self.x, self.y);
+        let mut new_guard = Guard::new(self.facing, self.position);
 
         loop {
             match new_guard.get_front(grid) {
-                Some(Point::Empty) => new_guard.forward(),
-                Some(Point::Visited) => {
+                Some(Cell::Empty) => new_guard.forward(),
+                Some(Cell::Visited) => {
                     new_guard.forward();
                 }
-                Some(Point::Obstacle) => {
+                Some(Cell::Obstacle) => {
                     if new_guard
                         .turns
-                        .contains(&(new_guard.facing, new_guard.x, new_guard.y))
+                        .contains(&(new_guard.facing, new_guard.position))
                     {
                         //print_grid_with_path(grid, &new_guard.path);
                         return true;
@@ -200,47 +187,43 @@ impl Guard {
     }
 }
 
-fn parse(input: &str) -> (Vec<Vec<Point>>, Guard) {
-    let mut grid = vec![];
+pub fn parse(input: &str) -> Result<Lab, ParseError> {
     let mut guard = None;
-    let mut location = (0, 0);
-
-    for line in input.lines() {
-        location.1 += 1;
-        location.0 = 0;
-        let mut cur_line = vec![];
-        for char in line.chars() {
-            location.0 += 1;
-            let point = match char {
-                '.' => Point::Empty,
-                '#' => Point::Obstacle,
-                '^' => {
-                    guard = Some(Guard::new(Direction::Up, location.0 - 1, location.1 - 1));
-                    Point::Visited
-                }
-                _ => todo!(), // This shouldn't happen
-            };
-            cur_line.push(point);
+
+    let grid = Grid::parse(DAY, input, |(x, y), char| match char {
+        '.' => Ok(Cell::Empty),
+        '#' => Ok(Cell::Obstacle),
+        '^' if guard.is_some() => Err("found a second guard".to_string()),
+        '^' => {
+            guard = Some(Guard::new(Dir4::Up, Point::from((x, y))));
+            Ok(Cell::Visited)
+        }
+        _ => Err(format!("unexpected {:?}, expected '.', '#' or '^'", char)),
+    })?;
+
+    match guard {
+        Some(guard) => Ok(Lab { grid, guard }),
+        None => {
+            let (y, line) = input.lines().enumerate().last().unwrap_or_default();
+            Err(ParseError::end(DAY, y, line, "no guard ('^') in the map"))
         }
-        grid.push(cur_line);
     }
-
-    (grid, guard.unwrap())
 }
 
-fn print_grid_with_path(grid: &[Vec<Point>], path: &[(usize, usize)]) {
-    let mut grid_copy = grid.to_vec();
-    for &(x, y) in path {
-        grid_copy[y][x] = Point::Visited; // Mark the path for visualization
+#[allow(dead_code)]
+fn print_grid_with_path(grid: &Grid<Cell>, path: &[Point]) {
+    let mut grid_copy = grid.clone();
+    for &position in path {
+        grid_copy[grid.index_of(position).unwrap()] = Cell::Visited; // Mark the path for visualization
     }
-    for row in grid_copy {
+    for row in grid_copy.rows() {
         println!(
             "{}",
             row.iter()
                 .map(|p| match p {
-                    Point::Empty => '.',
-                    Point::Visited => 'L', // 'L' for Loop path
-                    Point::Obstacle => '#',
+                    Cell::Empty => '.',
+                    Cell::Visited => 'L', // 'L' for Loop path
+                    Cell::Obstacle => '#',
                 })
                 .collect::<String>()
         );
@@ -251,6 +234,7 @@ fn print_grid_with_path(grid: &[Vec<Point>], path: &[(usize, usize)]) {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::time::Duration;
 
     const INPUT: &str = "\
 ....#.....
@@ -266,13 +250,36 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        let traveled = part1(INPUT);
-        assert_eq!(traveled, 41);
+        let traveled = part1(&parse(INPUT).unwrap(), &Token::never());
+        assert_eq!(traveled, Ok(41));
+    }
+
+    #[test]
+    fn test_part1_timeout() {
+        // The guard patrols this map in a circle forever
+        let looping = parse(".#..\n...#\n#^..\n..#.\n").unwrap();
+        let expired = Token::after(Some(Duration::from_millis(10)));
+        assert_eq!(part1(&looping, &expired), Err(Timeout));
     }
 
     #[test]
     fn test_part2() {
-        let blocks = part2(INPUT);
-        assert_eq!(blocks, 6);
+        let blocks = part2(&parse(INPUT).unwrap(), &Token::never());
+        assert_eq!(blocks, Ok(6));
+    }
+
+    #[test]
+    fn test_part2_timeout() {
+        let expired = Token::after(Some(Duration::ZERO));
+        assert_eq!(part2(&parse(INPUT).unwrap(), &expired), Err(Timeout));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = parse("..#\n.x^\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+
+        let err = parse("..#\n...\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 4));
     }
 }
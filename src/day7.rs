@@ -1,23 +1,58 @@
-pub fn run() {
-    let input = include_str!("../data/day7");
+use crate::answer::Answer;
+use crate::cancel::{Timeout, Token};
+use crate::error::ParseError;
+use crate::parser::{literal, parse_line, separated, unsigned};
+use crate::solution::Solution;
 
-    println!("Part 1: {}", part1(input));
-    println!("Part 2: {}", part2(input));
+const DAY: u32 = 7;
+
+pub struct Day7;
+
+/// Each equation's test value and the numbers that should make it.
+pub type Calibrations = Vec<(usize, Vec<usize>)>;
+
+/// The calibration equations, along with how many numbers the longest one
+/// has so the operator combinations can be built up front.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Equations {
+    pub calibrations: Calibrations,
+    pub biggest: usize,
 }
 
-fn part1(input: &str) -> usize {
-    let (parsed, biggest) = parse(input);
+impl Solution for Day7 {
+    type Input = Equations;
 
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input, cancel: &Token) -> Result<Answer, Timeout> {
+        part1(input, cancel).map(Answer::from)
+    }
+
+    fn part2(input: &Self::Input, cancel: &Token) -> Result<Answer, Timeout> {
+        part2(input, cancel).map(Answer::from)
+    }
+}
+
+pub fn part1(input: &Equations, cancel: &Token) -> Result<usize, Timeout> {
+    let Equations {
+        calibrations: parsed,
+        biggest,
+    } = input;
     let mut total = 0;
 
     let mut combos: Vec<Vec<Vec<Operation>>> = Vec::new();
 
-    for i in 0..biggest {
+    for i in 0..*biggest {
         let mut combinations = vec![vec![]];
 
         for _ in 0..i {
             let mut new_combinations = Vec::with_capacity(combinations.len() * 2);
             for combination in &combinations {
+                // There are 2^n of these, so long lines take a while
+                cancel.check()?;
+
                 let mut with_add = combination.clone();
                 with_add.push(Operation::Add);
                 new_combinations.push(with_add);
@@ -33,6 +68,8 @@ fn part1(input: &str) -> usize {
     }
 
     for calib in parsed {
+        cancel.check()?;
+
         for op_list in combos[calib.1.len() - 1].clone() {
             let mut val = calib.1[0];
             for (i, op) in op_list.iter().enumerate() {
@@ -50,22 +87,27 @@ fn part1(input: &str) -> usize {
         }
     }
 
-    total
+    Ok(total)
 }
 
-fn part2(input: &str) -> usize {
-    let (parsed, biggest) = parse(input);
-
+pub fn part2(input: &Equations, cancel: &Token) -> Result<usize, Timeout> {
+    let Equations {
+        calibrations: parsed,
+        biggest,
+    } = input;
     let mut total = 0;
 
     let mut combos: Vec<Vec<Vec<Operation>>> = Vec::new();
 
-    for i in 0..biggest {
+    for i in 0..*biggest {
         let mut combinations = vec![vec![]];
 
         for _ in 0..i {
             let mut new_combinations = Vec::with_capacity(combinations.len() * 2);
             for combination in &combinations {
+                // There are 3^n of these, so long lines take a while
+                cancel.check()?;
+
                 let mut with_add = combination.clone();
                 with_add.push(Operation::Add);
                 new_combinations.push(with_add);
@@ -85,6 +127,8 @@ fn part2(input: &str) -> usize {
     }
 
     for calib in parsed {
+        cancel.check()?;
+
         for op_list in combos[calib.1.len() - 1].clone() {
             let mut val = calib.1[0];
             for (i, op) in op_list.iter().enumerate() {
@@ -102,10 +146,11 @@ fn part2(input: &str) -> usize {
         }
     }
 
-    total
+    Ok(total)
 }
 
-fn concat(a: usize, b: usize) -> usize {
+/// Join the digits of two numbers, so `concat(12, 345)` is `12345`.
+pub fn concat(a: usize, b: usize) -> usize {
     let mut multiplier = 1;
     let mut temp = b;
 
@@ -124,28 +169,34 @@ enum Operation {
     Concat,
 }
 
-fn parse(input: &str) -> (Vec<(usize, Vec<usize>)>, usize) {
+pub fn parse(input: &str) -> Result<Equations, ParseError> {
     let mut parsed = Vec::new();
     let mut biggest = 0;
-    for line in input.lines() {
-        let str: Vec<&str> = line.split(": ").collect();
-        let test: usize = str[0].parse().expect("Couldn't parse test value as number");
-        let nums: Vec<usize> = str[1]
-            .split(" ")
-            .map(|x| x.parse().expect("Couldn't parse number"))
-            .collect();
+    let equation = (
+        unsigned(),
+        literal(": "),
+        separated(unsigned(), literal(" ")),
+    );
+
+    for (i, line) in input.lines().enumerate() {
+        let message = "expected an equation like 190: 10 19";
+        let (test, _, nums): (usize, _, Vec<usize>) = parse_line(DAY, i, line, &equation, message)?;
         parsed.push((test, nums.clone()));
         if nums.len() > biggest {
             biggest = nums.len();
         }
     }
 
-    (parsed, biggest)
+    Ok(Equations {
+        calibrations: parsed,
+        biggest,
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::time::Duration;
 
     const INPUT: &str = "\
 190: 10 19
@@ -160,11 +211,23 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1(INPUT), 3749);
+        assert_eq!(part1(&parse(INPUT).unwrap(), &Token::never()), Ok(3749));
+    }
+
+    #[test]
+    fn test_part1_timeout() {
+        let expired = Token::after(Some(Duration::ZERO));
+        assert_eq!(part1(&parse(INPUT).unwrap(), &expired), Err(Timeout));
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(INPUT), 11387);
+        assert_eq!(part2(&parse(INPUT).unwrap(), &Token::never()), Ok(11387));
+    }
+
+    #[test]
+    fn test_part2_timeout() {
+        let expired = Token::after(Some(Duration::ZERO));
+        assert_eq!(part2(&parse(INPUT).unwrap(), &expired), Err(Timeout));
     }
 }
@@ -0,0 +1,94 @@
+use std::error::Error;
+use std::fmt;
+
+/// A problem with a day's puzzle input, pointing at where it went wrong.
+#[derive(Debug, PartialEq)]
+pub struct ParseError {
+    pub day: u32,
+    /// One-based line number
+    pub line: usize,
+    /// One-based column, counted in characters
+    pub column: usize,
+    pub message: String,
+    /// The offending line, for showing the error in context
+    pub source_line: String,
+}
+
+impl ParseError {
+    /// `index` is the zero-based line index and `offset` a byte offset into
+    /// `line`.
+    pub fn new(
+        day: u32,
+        index: usize,
+        line: &str,
+        offset: usize,
+        message: impl Into<String>,
+    ) -> Self {
+        let offset = offset.min(line.len());
+        ParseError {
+            day,
+            line: index + 1,
+            column: line[..offset].chars().count() + 1,
+            message: message.into(),
+            source_line: line.to_string(),
+        }
+    }
+
+    /// An error pointing at `token`, which has to be a slice of `line`.
+    pub fn at(day: u32, index: usize, line: &str, token: &str, message: impl Into<String>) -> Self {
+        let offset = token.as_ptr() as usize - line.as_ptr() as usize;
+        ParseError::new(day, index, line, offset, message)
+    }
+
+    /// An error pointing just past the end of `line`, for when something is
+    /// missing.
+    pub fn end(day: u32, index: usize, line: &str, message: impl Into<String>) -> Self {
+        ParseError::new(day, index, line, line.len(), message)
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let gutter = " ".repeat(self.line.to_string().len());
+        writeln!(
+            f,
+            "Day {}, line {}, column {}: {}",
+            self.day, self.line, self.column, self.message
+        )?;
+        writeln!(f, "{} |", gutter)?;
+        writeln!(f, "{} | {}", self.line, self.source_line)?;
+        write!(f, "{} | {}^", gutter, " ".repeat(self.column - 1))
+    }
+}
+
+impl Error for ParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_at_token() {
+        let line = "83: 17 x5";
+        let token = line.split(' ').nth(2).unwrap();
+        let err = ParseError::at(7, 2, line, token, "expected a number");
+
+        assert_eq!(err.line, 3);
+        assert_eq!(err.column, 8);
+        assert_eq!(
+            err.to_string(),
+            "\
+Day 7, line 3, column 8: expected a number
+  |
+3 | 83: 17 x5
+  |        ^"
+        );
+    }
+
+    #[test]
+    fn test_end() {
+        let err = ParseError::end(1, 9, "12", "expected two numbers");
+        assert_eq!((err.line, err.column), (10, 3));
+        assert!(err.to_string().ends_with("\n   |   ^"));
+    }
+}
@@ -0,0 +1,462 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+use std::fmt;
+
+pub type NodeIndex = usize;
+pub type EdgeIndex = usize;
+
+/// A directed graph stored as per-node linked lists of edges, with a value
+/// of type `N` on every node and `E` on every edge.
+#[derive(Clone, Debug)]
+pub struct Graph<N = (), E = ()> {
+    nodes: Vec<NodeData<N>>,
+    edges: Vec<EdgeData<E>>,
+}
+
+#[derive(Clone, Debug)]
+struct NodeData<N> {
+    data: N,
+    first_outgoing_edge: Option<EdgeIndex>,
+    first_incoming_edge: Option<EdgeIndex>,
+}
+
+#[derive(Clone, Debug)]
+struct EdgeData<E> {
+    data: E,
+    source: NodeIndex,
+    target: NodeIndex,
+    next_outgoing_edge: Option<EdgeIndex>,
+    next_incoming_edge: Option<EdgeIndex>,
+}
+
+/// The nodes around a cycle that stopped a topological sort, in edge order
+/// from the lowest index.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Cycle(pub Vec<NodeIndex>);
+
+impl fmt::Display for Cycle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "cycle through nodes")?;
+        for node in self.0.iter().chain(self.0.first()) {
+            write!(f, " {}", node)?;
+        }
+        Ok(())
+    }
+}
+
+impl<N, E> Default for Graph<N, E> {
+    fn default() -> Self {
+        Graph {
+            nodes: vec![],
+            edges: vec![],
+        }
+    }
+}
+
+impl<N, E> Graph<N, E> {
+    pub fn new() -> Self {
+        Graph::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    pub fn add_node(&mut self, data: N) -> NodeIndex {
+        let index = self.nodes.len();
+        self.nodes.push(NodeData {
+            data,
+            first_outgoing_edge: None,
+            first_incoming_edge: None,
+        });
+        index
+    }
+
+    pub fn add_edge(&mut self, source: NodeIndex, target: NodeIndex, data: E) -> EdgeIndex {
+        let edge_index = self.edges.len();
+        self.edges.push(EdgeData {
+            data,
+            source,
+            target,
+            next_outgoing_edge: self.nodes[source].first_outgoing_edge,
+            next_incoming_edge: self.nodes[target].first_incoming_edge,
+        });
+        self.nodes[source].first_outgoing_edge = Some(edge_index);
+        self.nodes[target].first_incoming_edge = Some(edge_index);
+        edge_index
+    }
+
+    pub fn node(&self, node: NodeIndex) -> &N {
+        &self.nodes[node].data
+    }
+
+    pub fn node_mut(&mut self, node: NodeIndex) -> &mut N {
+        &mut self.nodes[node].data
+    }
+
+    pub fn edge(&self, edge: EdgeIndex) -> &E {
+        &self.edges[edge].data
+    }
+
+    /// The `(source, target)` nodes of an edge.
+    pub fn endpoints(&self, edge: EdgeIndex) -> (NodeIndex, NodeIndex) {
+        let edge = &self.edges[edge];
+        (edge.source, edge.target)
+    }
+
+    /// The edges leaving `source`, most recently added first.
+    pub fn outgoing(&self, source: NodeIndex) -> Edges<'_, N, E> {
+        Edges {
+            graph: self,
+            current_edge_index: self.nodes[source].first_outgoing_edge,
+            incoming: false,
+        }
+    }
+
+    /// The edges arriving at `target`, most recently added first.
+    pub fn incoming(&self, target: NodeIndex) -> Edges<'_, N, E> {
+        Edges {
+            graph: self,
+            current_edge_index: self.nodes[target].first_incoming_edge,
+            incoming: true,
+        }
+    }
+
+    pub fn successors(&self, source: NodeIndex) -> impl Iterator<Item = NodeIndex> + '_ {
+        self.outgoing(source).map(|edge| self.edges[edge].target)
+    }
+
+    pub fn predecessors(&self, target: NodeIndex) -> impl Iterator<Item = NodeIndex> + '_ {
+        self.incoming(target).map(|edge| self.edges[edge].source)
+    }
+
+    /// Every node reachable from `start` in breadth-first order, starting
+    /// with `start` itself.
+    pub fn bfs(&self, start: NodeIndex) -> Vec<NodeIndex> {
+        let mut seen = vec![false; self.len()];
+        let mut queue = VecDeque::from([start]);
+        let mut order = vec![];
+        seen[start] = true;
+
+        while let Some(node) = queue.pop_front() {
+            order.push(node);
+            for next in self.successors(node) {
+                if !seen[next] {
+                    seen[next] = true;
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        order
+    }
+
+    /// Every node reachable from `start` in depth-first preorder, starting
+    /// with `start` itself.
+    pub fn dfs(&self, start: NodeIndex) -> Vec<NodeIndex> {
+        let mut seen = vec![false; self.len()];
+        let mut stack = vec![start];
+        let mut order = vec![];
+
+        while let Some(node) = stack.pop() {
+            if seen[node] {
+                continue;
+            }
+            seen[node] = true;
+            order.push(node);
+
+            // Reversed so successors are visited in the order they're listed
+            let successors: Vec<_> = self.successors(node).collect();
+            stack.extend(successors.into_iter().rev().filter(|&next| !seen[next]));
+        }
+
+        order
+    }
+
+    /// Which nodes can be reached from `start`, indexed by node. Every node
+    /// can reach itself.
+    pub fn reachable(&self, start: NodeIndex) -> Vec<bool> {
+        let mut reachable = vec![false; self.len()];
+        for node in self.dfs(start) {
+            reachable[node] = true;
+        }
+        reachable
+    }
+
+    pub fn is_reachable(&self, from: NodeIndex, to: NodeIndex) -> bool {
+        from == to || self.dfs(from).contains(&to)
+    }
+
+    /// The length of the cheapest path from `start` to every node, or `None`
+    /// where there isn't one. `cost` gives the cost of taking an edge.
+    pub fn dijkstra(&self, start: NodeIndex, cost: impl Fn(&E) -> u64) -> Vec<Option<u64>> {
+        let mut distances = vec![None; self.len()];
+        let mut queue = BinaryHeap::from([Reverse((0, start))]);
+
+        while let Some(Reverse((distance, node))) = queue.pop() {
+            if distances[node].is_some() {
+                continue; // Already found a cheaper way here
+            }
+            distances[node] = Some(distance);
+
+            for edge in self.outgoing(node) {
+                let EdgeData { data, target, .. } = &self.edges[edge];
+                if distances[*target].is_none() {
+                    queue.push(Reverse((distance + cost(data), *target)));
+                }
+            }
+        }
+
+        distances
+    }
+
+    /// Order the nodes so every edge points forwards, breaking ties by the
+    /// lowest index. Fails with one of the cycles if there's no such order.
+    pub fn topological_sort(&self) -> Result<Vec<NodeIndex>, Cycle> {
+        // Track in-degrees of all nodes
+        let mut in_degree = vec![0; self.len()];
+        for edge in &self.edges {
+            in_degree[edge.target] += 1;
+        }
+
+        // Start from the nodes with no prerequisites
+        let mut queue: BinaryHeap<_> = (0..self.len())
+            .filter(|&node| in_degree[node] == 0)
+            .map(Reverse)
+            .collect();
+        let mut sorted = Vec::with_capacity(self.len());
+
+        while let Some(Reverse(node)) = queue.pop() {
+            sorted.push(node);
+            for successor in self.successors(node) {
+                in_degree[successor] -= 1;
+                if in_degree[successor] == 0 {
+                    queue.push(Reverse(successor));
+                }
+            }
+        }
+
+        match (0..self.len()).find(|&node| in_degree[node] > 0) {
+            Some(stuck) => Err(self.cycle_before(stuck, &in_degree)),
+            None => Ok(sorted),
+        }
+    }
+
+    /// Find a cycle by walking backwards from a node the sort got stuck on.
+    /// Every stuck node has a stuck predecessor, so the walk has to repeat.
+    fn cycle_before(&self, start: NodeIndex, in_degree: &[usize]) -> Cycle {
+        let mut walk = vec![start];
+        let mut position = vec![None; self.len()];
+        position[start] = Some(0);
+
+        loop {
+            let node = walk[walk.len() - 1];
+            let previous = self
+                .predecessors(node)
+                .find(|&previous| in_degree[previous] > 0)
+                .expect("stuck nodes have a stuck predecessor");
+
+            if let Some(i) = position[previous] {
+                let mut cycle = walk.split_off(i);
+                cycle.reverse(); // The walk went against the edges
+                let lowest = (0..cycle.len()).min_by_key(|&i| cycle[i]).unwrap();
+                cycle.rotate_left(lowest);
+                return Cycle(cycle);
+            }
+            position[previous] = Some(walk.len());
+            walk.push(previous);
+        }
+    }
+
+    /// Group the nodes into strongly connected components, where every node
+    /// can reach every other. Components are listed so that edges between
+    /// them only point forwards, with each one's nodes in index order.
+    pub fn strongly_connected_components(&self) -> Vec<Vec<NodeIndex>> {
+        // Kosaraju: finish order on the graph, then sweep the reversed graph
+        let mut seen = vec![false; self.len()];
+        let mut finished = Vec::with_capacity(self.len());
+
+        for root in 0..self.len() {
+            if seen[root] {
+                continue;
+            }
+            seen[root] = true;
+            let mut stack = vec![(root, self.successors(root))];
+
+            while let Some((node, successors)) = stack.last_mut() {
+                let node = *node;
+                match successors.next() {
+                    Some(next) if !seen[next] => {
+                        seen[next] = true;
+                        stack.push((next, self.successors(next)));
+                    }
+                    Some(_) => {}
+                    None => {
+                        finished.push(node);
+                        stack.pop();
+                    }
+                }
+            }
+        }
+
+        let mut assigned = vec![false; self.len()];
+        let mut components = vec![];
+
+        for &root in finished.iter().rev() {
+            if assigned[root] {
+                continue;
+            }
+            assigned[root] = true;
+            let mut component = vec![];
+            let mut stack = vec![root];
+
+            while let Some(node) = stack.pop() {
+                component.push(node);
+                for previous in self.predecessors(node) {
+                    if !assigned[previous] {
+                        assigned[previous] = true;
+                        stack.push(previous);
+                    }
+                }
+            }
+
+            component.sort_unstable();
+            components.push(component);
+        }
+
+        components
+    }
+}
+
+/// Walks one of a node's edge lists.
+pub struct Edges<'graph, N, E> {
+    graph: &'graph Graph<N, E>,
+    current_edge_index: Option<EdgeIndex>,
+    incoming: bool,
+}
+
+impl<'graph, N, E> Iterator for Edges<'graph, N, E> {
+    type Item = EdgeIndex;
+
+    fn next(&mut self) -> Option<EdgeIndex> {
+        let edge_num = self.current_edge_index?;
+        let edge = &self.graph.edges[edge_num];
+        self.current_edge_index = match self.incoming {
+            true => edge.next_incoming_edge,
+            false => edge.next_outgoing_edge,
+        };
+        Some(edge_num)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A graph with nodes 0..n and an edge for each pair, weighted by `cost`.
+    fn graph(n: usize, edges: &[(NodeIndex, NodeIndex, u64)]) -> Graph<(), u64> {
+        let mut graph = Graph::new();
+        for _ in 0..n {
+            graph.add_node(());
+        }
+        for &(source, target, cost) in edges {
+            graph.add_edge(source, target, cost);
+        }
+        graph
+    }
+
+    fn sorted(nodes: impl Iterator<Item = NodeIndex>) -> Vec<NodeIndex> {
+        let mut nodes: Vec<_> = nodes.collect();
+        nodes.sort_unstable();
+        nodes
+    }
+
+    #[test]
+    fn test_payloads() {
+        let mut graph = Graph::new();
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        let c = graph.add_node("c");
+        let ab = graph.add_edge(a, b, 1.5);
+        graph.add_edge(c, b, 2.5);
+        *graph.node_mut(c) = "see";
+
+        assert_eq!((graph.len(), graph.edge_count()), (3, 2));
+        assert_eq!(graph.node(c), &"see");
+        assert_eq!(graph.edge(ab), &1.5);
+        assert_eq!(graph.endpoints(ab), (a, b));
+        assert_eq!(sorted(graph.predecessors(b)), [a, c]);
+        assert_eq!(sorted(graph.successors(a)), [b]);
+        assert_eq!(graph.predecessors(a).count(), 0);
+    }
+
+    #[test]
+    fn test_bfs_and_dfs() {
+        // 0 -> 1 -> 3, 0 -> 2 -> 3, and 4 off on its own
+        let graph = graph(5, &[(0, 2, 1), (0, 1, 1), (1, 3, 1), (2, 3, 1)]);
+
+        assert_eq!(graph.bfs(0), [0, 1, 2, 3]);
+        assert_eq!(graph.dfs(0), [0, 1, 3, 2]);
+        assert_eq!(graph.bfs(4), [4]);
+    }
+
+    #[test]
+    fn test_reachable() {
+        let graph = graph(4, &[(0, 1, 1), (1, 2, 1), (3, 2, 1)]);
+
+        assert_eq!(graph.reachable(0), [true, true, true, false]);
+        assert!(graph.is_reachable(0, 2));
+        assert!(!graph.is_reachable(2, 0));
+        assert!(graph.is_reachable(3, 3));
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        let graph = graph(5, &[(0, 1, 7), (0, 2, 2), (2, 1, 3), (1, 3, 1), (3, 0, 9)]);
+
+        let distances = graph.dijkstra(0, |&cost| cost);
+        assert_eq!(distances, [Some(0), Some(5), Some(2), Some(6), None]);
+    }
+
+    #[test]
+    fn test_topological_sort() {
+        let graph = graph(4, &[(3, 1, 1), (1, 0, 1), (2, 0, 1)]);
+        assert_eq!(graph.topological_sort(), Ok(vec![2, 3, 1, 0]));
+
+        // 0 -> 1 -> 2 -> 3 -> 1, so 1, 2 and 3 are stuck
+        let graph = self::graph(4, &[(0, 1, 1), (1, 2, 1), (2, 3, 1), (3, 1, 1)]);
+        let cycle = graph.topological_sort().unwrap_err();
+        assert_eq!(cycle, Cycle(vec![1, 2, 3]));
+        assert_eq!(cycle.to_string(), "cycle through nodes 1 2 3 1");
+    }
+
+    #[test]
+    fn test_strongly_connected_components() {
+        // {0, 1, 2} loop and lead into {3, 4}, which lead into 5
+        let graph = graph(
+            6,
+            &[
+                (0, 1, 1),
+                (1, 2, 1),
+                (2, 0, 1),
+                (2, 3, 1),
+                (3, 4, 1),
+                (4, 3, 1),
+                (4, 5, 1),
+            ],
+        );
+
+        assert_eq!(
+            graph.strongly_connected_components(),
+            [vec![0, 1, 2], vec![3, 4], vec![5]]
+        );
+    }
+}
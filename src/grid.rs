@@ -0,0 +1,297 @@
+use std::ops::{Index, IndexMut};
+
+use crate::error::ParseError;
+use crate::point::{Dir4, Dir8, Point};
+
+/// A rectangular grid stored row by row in one `Vec`. Positions are `(x, y)`
+/// with `(0, 0)` in the top left corner.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Build a grid from its cells in row-major order.
+    ///
+    /// Panics if either dimension is zero, or if there aren't exactly
+    /// `width * height` cells.
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert!(width > 0 && height > 0, "grid needs at least one cell");
+        assert_eq!(cells.len(), width * height, "grid is the wrong size");
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Panics if either dimension is zero.
+    pub fn filled(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid::new(width, height, vec![value; width * height])
+    }
+
+    /// Parse one cell per character, with a row per line. `cell` gets each
+    /// character's position and says what it means, or why it's wrong. Rows
+    /// all have to be the same length, and there has to be at least one.
+    pub fn parse(
+        day: u32,
+        input: &str,
+        mut cell: impl FnMut((usize, usize), char) -> Result<T, String>,
+    ) -> Result<Self, ParseError> {
+        let mut cells = vec![];
+        let mut width = 0;
+        let mut height = 0;
+
+        for (y, line) in input.lines().enumerate() {
+            let mut x = 0;
+            for (offset, c) in line.char_indices() {
+                if y > 0 && x == width {
+                    let message = format!("expected {} cells like the first line", width);
+                    return Err(ParseError::new(day, y, line, offset, message));
+                }
+                let value =
+                    cell((x, y), c).map_err(|msg| ParseError::new(day, y, line, offset, msg))?;
+                cells.push(value);
+                x += 1;
+            }
+
+            if y == 0 {
+                width = x;
+            } else if x != width {
+                let message = format!("expected {} cells like the first line", width);
+                return Err(ParseError::end(day, y, line, message));
+            }
+            height += 1;
+        }
+
+        if width == 0 {
+            return Err(ParseError::end(day, 0, "", "expected a grid"));
+        }
+
+        Ok(Grid::new(width, height, cells))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, (x, y): (usize, usize)) -> Option<&T> {
+        (x < self.width && y < self.height).then(|| &self.cells[y * self.width + x])
+    }
+
+    pub fn get_mut(&mut self, (x, y): (usize, usize)) -> Option<&mut T> {
+        (x < self.width && y < self.height).then(|| &mut self.cells[y * self.width + x])
+    }
+
+    /// The cell at a position that wraps around the edges, as if the grid
+    /// were tiled forever in every direction.
+    pub fn get_wrapping(&self, x: isize, y: isize) -> &T {
+        let x = x.rem_euclid(self.width as isize) as usize;
+        let y = y.rem_euclid(self.height as isize) as usize;
+        &self[(x, y)]
+    }
+
+    /// The grid indices of `point`, if it's on the grid.
+    pub fn index_of(&self, point: Point) -> Option<(usize, usize)> {
+        let (x, y) = point.to_index()?;
+        (x < self.width && y < self.height).then_some((x, y))
+    }
+
+    pub fn get_point(&self, point: Point) -> Option<&T> {
+        self.get(self.index_of(point)?)
+    }
+
+    /// The position one `step` away from `position`, if it's on the grid.
+    pub fn offset(
+        &self,
+        position: (usize, usize),
+        step: impl Into<Point>,
+    ) -> Option<(usize, usize)> {
+        self.index_of(Point::from(position).step(step, 1))
+    }
+
+    /// The positions next to `position` that share an edge with it.
+    pub fn neighbours4(
+        &self,
+        position: (usize, usize),
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        Dir4::ALL
+            .into_iter()
+            .filter_map(move |direction| self.offset(position, direction))
+    }
+
+    /// The positions next to `position`, including diagonally.
+    pub fn neighbours8(
+        &self,
+        position: (usize, usize),
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        Dir8::ALL
+            .into_iter()
+            .filter_map(move |direction| self.offset(position, direction))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks_exact(self.width)
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {} is off the grid", x);
+        self.cells[x..].iter().step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Every position in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |i| (i % width, i / width))
+    }
+
+    /// Every cell along with its position, in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    /// The position of the first cell that matches, in row-major order.
+    pub fn position(&self, predicate: impl Fn(&T) -> bool) -> Option<(usize, usize)> {
+        let i = self.cells.iter().position(predicate)?;
+        Some((i % self.width, i / self.width))
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: (usize, usize)) -> &T {
+        self.get(position)
+            .unwrap_or_else(|| panic!("{:?} is off the grid", position))
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, position: (usize, usize)) -> &mut T {
+        self.get_mut(position)
+            .unwrap_or_else(|| panic!("{:?} is off the grid", position))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn letters(input: &str) -> Grid<char> {
+        Grid::parse(0, input, |_, c| Ok(c)).unwrap()
+    }
+
+    #[test]
+    fn test_get() {
+        let grid = letters("abc\ndef\n");
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get((2, 1)), Some(&'f'));
+        assert_eq!(grid.get((3, 0)), None); // Doesn't spill into the next row
+        assert_eq!(grid.get((0, 2)), None);
+        assert_eq!(grid[(1, 0)], 'b');
+
+        assert_eq!(grid.get_wrapping(3, 0), &'a');
+        assert_eq!(grid.get_wrapping(-1, -1), &'f');
+        assert_eq!(grid.get_wrapping(-7, 5), &'f');
+
+        assert_eq!(grid.get_point(Point::new(0, 1)), Some(&'d'));
+        assert_eq!(grid.get_point(Point::new(-1, 1)), None);
+        assert_eq!(grid.index_of(Point::new(3, 1)), None);
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = letters("abc\ndef\nghi\n");
+        let cells = |positions: Vec<(usize, usize)>| -> String {
+            positions.into_iter().map(|p| grid[p]).collect()
+        };
+
+        assert_eq!(cells(grid.neighbours4((1, 1)).collect()), "bfhd");
+        assert_eq!(cells(grid.neighbours8((1, 1)).collect()), "bcfihgda");
+        assert_eq!(cells(grid.neighbours4((0, 0)).collect()), "bd");
+        assert_eq!(cells(grid.neighbours8((2, 2)).collect()), "fhe");
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = letters("abc\ndef\n");
+        let rows: Vec<String> = grid.rows().map(|row| row.iter().collect()).collect();
+        assert_eq!(rows, ["abc", "def"]);
+        assert_eq!(grid.row(1), ['d', 'e', 'f']);
+
+        let columns: Vec<String> = grid.columns().map(|column| column.collect()).collect();
+        assert_eq!(columns, ["ad", "be", "cf"]);
+    }
+
+    #[test]
+    fn test_iter() {
+        let mut grid = letters("ab\ncd\n");
+        assert_eq!(grid.position(|&c| c == 'c'), Some((0, 1)));
+        grid[(0, 1)] = 'x';
+        let cells: Vec<_> = grid.iter().collect();
+        assert_eq!(
+            cells,
+            [
+                ((0, 0), &'a'),
+                ((1, 0), &'b'),
+                ((0, 1), &'x'),
+                ((1, 1), &'d')
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = Grid::parse(0, "#.\n.#\n", |_, c| match c {
+            '#' => Ok(true),
+            '.' => Ok(false),
+            _ => Err(format!("unexpected {:?}", c)),
+        })
+        .unwrap();
+        assert_eq!(grid, Grid::new(2, 2, vec![true, false, false, true]));
+
+        let err = Grid::parse(0, "ab\nc?\n", |_, c| match c {
+            '?' => Err("no".to_string()),
+            c => Ok(c),
+        })
+        .unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+
+        let err = Grid::parse(0, "abc\nabcd\n", |_, c| Ok(c)).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 4));
+
+        let err = Grid::parse(0, "abc\nab\n", |_, c| Ok(c)).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+
+        assert!(Grid::parse(0, "", |_, c| Ok(c)).is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "grid needs at least one cell")]
+    fn test_empty() {
+        Grid::<char>::new(0, 3, vec![]);
+    }
+
+    #[test]
+    #[should_panic(expected = "grid needs at least one cell")]
+    fn test_empty_filled() {
+        Grid::filled(4, 0, '.');
+    }
+}
@@ -0,0 +1,210 @@
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::process::{Command, Stdio};
+
+const USER_AGENT: &str = "github.com/realprogrammersusevim/aoc-2024 runner";
+
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+/// Make a request with the session cookie, posting `form` if there is one.
+/// Plain `http://` URLs (like a local stub) are handled here; anything else
+/// goes through curl.
+pub fn request(url: &str, session: &str, form: Option<&str>) -> Result<Response, String> {
+    match url.strip_prefix("http://") {
+        Some(rest) => plain(url, rest, session, form),
+        None => curl(url, session, form),
+    }
+}
+
+/// A bare HTTP/1.1 request over a TCP socket, for servers without TLS.
+fn plain(url: &str, rest: &str, session: &str, form: Option<&str>) -> Result<Response, String> {
+    let (host, path) = match rest.find('/') {
+        Some(i) => rest.split_at(i),
+        None => (rest, "/"),
+    };
+    let address = match host.contains(':') {
+        true => host.to_string(),
+        false => format!("{}:80", host),
+    };
+    let failed = |err: std::io::Error| format!("Request to {} failed: {}", url, err);
+
+    let mut request = format!(
+        "{} {} HTTP/1.1\r\nHost: {}\r\nUser-Agent: {}\r\nCookie: session={}\r\nConnection: close\r\n",
+        if form.is_some() { "POST" } else { "GET" },
+        path,
+        host,
+        USER_AGENT,
+        session
+    );
+    if let Some(form) = form {
+        request.push_str("Content-Type: application/x-www-form-urlencoded\r\n");
+        request.push_str(&format!("Content-Length: {}\r\n", form.len()));
+    }
+    request.push_str("\r\n");
+    request.push_str(form.unwrap_or_default());
+
+    let mut stream = TcpStream::connect(&address).map_err(failed)?;
+    stream.write_all(request.as_bytes()).map_err(failed)?;
+    let mut response = vec![];
+    stream.read_to_end(&mut response).map_err(failed)?;
+    let response = String::from_utf8_lossy(&response);
+
+    let malformed = || format!("Couldn't understand the response from {}", url);
+    let (head, body) = response.split_once("\r\n\r\n").ok_or_else(malformed)?;
+    let status = head
+        .split(' ')
+        .nth(1)
+        .and_then(|status| status.parse().ok())
+        .ok_or_else(malformed)?;
+    if head.to_lowercase().contains("transfer-encoding: chunked") {
+        return Err(format!(
+            "{} sent a chunked response, which isn't supported",
+            url
+        ));
+    }
+
+    Ok(Response {
+        status,
+        body: body.to_string(),
+    })
+}
+
+/// Shell out to curl, which saves us pulling in a TLS stack. The session
+/// cookie goes through stdin so it never shows up in the process list.
+fn curl(url: &str, session: &str, form: Option<&str>) -> Result<Response, String> {
+    let mut command = Command::new("curl");
+    command
+        .args(["--silent", "--show-error"])
+        .args(["--user-agent", USER_AGENT])
+        .args(["--header", "@-"])
+        .args(["--write-out", "\n%{http_code}"]);
+    if let Some(form) = form {
+        command.args(["--data", form]);
+    }
+    command
+        .arg(url)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+
+    let mut child = command
+        .spawn()
+        .map_err(|err| format!("Couldn't run curl (is it installed?): {}", err))?;
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(format!("Cookie: session={}\n", session).as_bytes())
+        .map_err(|err| format!("Couldn't talk to curl: {}", err))?;
+
+    let output = child
+        .wait_with_output()
+        .map_err(|err| format!("Couldn't run curl: {}", err))?;
+    if !output.status.success() {
+        return Err(format!(
+            "Request to {} failed: {}",
+            url,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    // The status code is the last line, thanks to --write-out
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let (body, status) = stdout.rsplit_once('\n').unwrap_or(("", &stdout));
+    let status = status
+        .parse()
+        .map_err(|_| format!("Couldn't read the status code from {}", url))?;
+
+    Ok(Response {
+        status,
+        body: body.to_string(),
+    })
+}
+
+/// A tiny HTTP server for tests that answers every request with a canned
+/// response and records what it was sent.
+#[cfg(test)]
+pub mod stub {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+
+    pub struct Server {
+        pub url: String,
+        pub requests: Arc<Mutex<Vec<String>>>,
+    }
+
+    pub fn serve(status: u16, body: &'static str) -> Server {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(vec![]));
+
+        let recorded = Arc::clone(&requests);
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(&stream);
+
+                let mut request = String::new();
+                let mut length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some(value) = line.to_lowercase().strip_prefix("content-length:") {
+                        length = value.trim().parse().unwrap();
+                    }
+                    request.push_str(&line);
+                    if line == "\r\n" || line.is_empty() {
+                        break;
+                    }
+                }
+                let mut form = vec![0; length];
+                reader.read_exact(&mut form).unwrap();
+                request.push_str(&String::from_utf8_lossy(&form));
+                recorded.lock().unwrap().push(request);
+
+                write!(
+                    stream,
+                    "HTTP/1.1 {} Stub\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                )
+                .unwrap();
+            }
+        });
+
+        Server { url, requests }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get() {
+        let server = stub::serve(200, "1 2\n3 4\n");
+        let response = request(&format!("{}/input", server.url), "abc", None).unwrap();
+
+        assert_eq!(response.status, 200);
+        assert_eq!(response.body, "1 2\n3 4\n");
+
+        let requests = server.requests.lock().unwrap();
+        assert!(requests[0].starts_with("GET /input HTTP/1.1"));
+        assert!(requests[0].contains("Cookie: session=abc"));
+    }
+
+    #[test]
+    fn test_post() {
+        let server = stub::serve(404, "");
+        let response = request(&server.url, "abc", Some("level=1&answer=42")).unwrap();
+
+        assert_eq!(response.status, 404);
+        assert!(server.requests.lock().unwrap()[0].ends_with("\r\n\r\nlevel=1&answer=42"));
+    }
+}
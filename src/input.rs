@@ -0,0 +1,122 @@
+use std::fs::{read_dir, read_to_string};
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::config;
+
+// Generated by build.rs from whatever inputs exist under data/ at build time
+#[cfg(feature = "embed-inputs")]
+include!(concat!(env!("OUT_DIR"), "/inputs.rs"));
+
+/// Where to read a day's puzzle input from.
+#[derive(Debug, PartialEq)]
+pub enum Source {
+    /// The day's usual file in the data directory
+    Default,
+    Path(String),
+    Stdin,
+}
+
+/// Load the puzzle input for a day from `source`. Inputs are read at runtime
+/// unless the `embed-inputs` feature compiled the day's default input in.
+pub fn load(day: u32, source: &Source) -> io::Result<String> {
+    match source {
+        Source::Default => match embedded(day) {
+            Some(input) => Ok(input.to_string()),
+            None => read_to_string(path(day)),
+        },
+        Source::Path(path) => read_to_string(path),
+        Source::Stdin => io::read_to_string(io::stdin()),
+    }
+}
+
+/// The default location of a day's input.
+pub fn path(day: u32) -> String {
+    config::get().path(&format!("day{}", day))
+}
+
+/// The directory `source` points at, if it's a directory of inputs rather
+/// than a single one.
+pub fn directory(day: u32, source: &Source) -> Option<PathBuf> {
+    let path = match source {
+        Source::Default if embedded(day).is_some() => return None,
+        Source::Default => PathBuf::from(path(day)),
+        Source::Path(path) => PathBuf::from(path),
+        Source::Stdin => return None,
+    };
+    path.is_dir().then_some(path)
+}
+
+/// Every input in a directory in name order, leaving out answer files and
+/// hidden files.
+pub fn files(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut files = vec![];
+    for entry in read_dir(dir)? {
+        let path = entry?.path();
+        let hidden = path
+            .file_name()
+            .is_some_and(|name| name.to_string_lossy().starts_with('.'));
+        let answers = path.extension().is_some_and(|ext| ext == "answers");
+        if path.is_file() && !hidden && !answers {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Where the expected answers for an input are kept: next to it, with an
+/// `.answers` extension.
+pub fn answers_path(input: &Path) -> PathBuf {
+    input.with_extension("answers")
+}
+
+/// A human readable name for where a day's input comes from.
+pub fn name(day: u32, source: &Source) -> String {
+    match source {
+        Source::Default if embedded(day).is_some() => format!("embedded {}", path(day)),
+        Source::Default => path(day),
+        Source::Path(path) => path.clone(),
+        Source::Stdin => "stdin".to_string(),
+    }
+}
+
+#[cfg(feature = "embed-inputs")]
+fn embedded(day: u32) -> Option<&'static str> {
+    INPUTS
+        .iter()
+        .find(|&&(number, _)| number == day)
+        .map(|&(_, input)| input)
+}
+
+#[cfg(not(feature = "embed-inputs"))]
+fn embedded(_day: u32) -> Option<&'static str> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_dir;
+    use std::fs::{create_dir_all, write};
+
+    #[test]
+    fn test_files() {
+        let dir = temp_dir("inputs");
+        create_dir_all(dir.join("nested")).unwrap();
+        for name in ["bob.txt", "alice.txt", "alice.answers", ".hidden"] {
+            write(dir.join(name), "").unwrap();
+        }
+
+        assert_eq!(
+            files(&dir).unwrap(),
+            vec![dir.join("alice.txt"), dir.join("bob.txt")]
+        );
+        assert_eq!(answers_path(&dir.join("bob.txt")), dir.join("bob.answers"));
+
+        let source = Source::Path(dir.display().to_string());
+        assert_eq!(directory(6, &source), Some(dir.clone()));
+        let source = Source::Path(dir.join("bob.txt").display().to_string());
+        assert_eq!(directory(6, &source), None);
+    }
+}
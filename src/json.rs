@@ -0,0 +1,157 @@
+use std::fmt;
+
+use crate::answer::Answer;
+
+/// Just enough JSON to print results for scripts to pick up.
+#[derive(Debug, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Number(i128),
+    String(String),
+    Array(Vec<Value>),
+    Object(Vec<(&'static str, Value)>),
+}
+
+impl From<bool> for Value {
+    fn from(value: bool) -> Self {
+        Value::Bool(value)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(value: &str) -> Self {
+        Value::String(value.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from(value: String) -> Self {
+        Value::String(value)
+    }
+}
+
+macro_rules! from_number {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Value {
+            fn from(value: $t) -> Self {
+                Value::Number(value as i128)
+            }
+        })*
+    };
+}
+
+from_number!(u32, usize, u64, i64, i128);
+
+impl From<u128> for Value {
+    fn from(value: u128) -> Self {
+        // Only answers get this big, and a string keeps every digit
+        i128::try_from(value).map_or_else(|_| Value::String(value.to_string()), Value::Number)
+    }
+}
+
+/// Numbers stay numbers so scripts don't have to parse them back out.
+impl From<&Answer> for Value {
+    fn from(value: &Answer) -> Self {
+        match value {
+            Answer::I64(n) => (*n).into(),
+            Answer::U64(n) => (*n).into(),
+            Answer::I128(n) => (*n).into(),
+            Answer::U128(n) => (*n).into(),
+            Answer::String(s) => s.as_str().into(),
+        }
+    }
+}
+
+impl<T: Into<Value>> From<Option<T>> for Value {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Value::Null, Into::into)
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Null => write!(f, "null"),
+            Value::Bool(value) => write!(f, "{}", value),
+            Value::Number(value) => write!(f, "{}", value),
+            Value::String(value) => write_string(f, value),
+            Value::Array(values) => {
+                write!(f, "[")?;
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", value)?;
+                }
+                write!(f, "]")
+            }
+            Value::Object(fields) => {
+                write!(f, "{{")?;
+                for (i, (key, value)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write_string(f, key)?;
+                    write!(f, ":{}", value)?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+fn write_string(f: &mut fmt::Formatter, value: &str) -> fmt::Result {
+    write!(f, "\"")?;
+    for c in value.chars() {
+        match c {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            '\r' => write!(f, "\\r")?,
+            '\t' => write!(f, "\\t")?,
+            c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{}", c)?,
+        }
+    }
+    write!(f, "\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let value = Value::Object(vec![
+            ("day", 6u32.into()),
+            ("ok", true.into()),
+            ("error", None::<String>.into()),
+            (
+                "parts",
+                Value::Array(vec![Value::Number(41), Value::String("a,b".into())]),
+            ),
+        ]);
+        assert_eq!(
+            value.to_string(),
+            r#"{"day":6,"ok":true,"error":null,"parts":[41,"a,b"]}"#
+        );
+    }
+
+    #[test]
+    fn test_escape() {
+        let value = Value::from("line \"1\"\n\tcol\\2\u{1}");
+        assert_eq!(value.to_string(), r#""line \"1\"\n\tcol\\2\u0001""#);
+    }
+
+    #[test]
+    fn test_answer() {
+        assert_eq!(Value::from(&Answer::from(41usize)).to_string(), "41");
+        assert_eq!(Value::from(&Answer::from(-3i64)).to_string(), "-3");
+        assert_eq!(Value::from(&Answer::from("6,0")).to_string(), r#""6,0""#);
+        assert_eq!(
+            Value::from(&Answer::U128(u128::MAX)).to_string(),
+            format!("\"{}\"", u128::MAX)
+        );
+    }
+}
@@ -0,0 +1,55 @@
+//! Solutions to Advent of Code 2024. Each `dayN` module has a `parse` that
+//! turns the puzzle input into a typed struct, and a `part1` and `part2` that
+//! solve it. The other modules are what the command line runner is built on.
+//!
+//! ```
+//! use aoc_2024::day5;
+//!
+//! let queue = day5::parse("47|53\n\n53,47\n").unwrap();
+//! assert_eq!(day5::sort_by_rules(&queue.updates[0], &queue.rules), Ok(vec![47, 53]));
+//! assert_eq!(day5::part2(&queue), Ok(53));
+//! ```
+
+use solution::Day;
+
+pub mod answer;
+pub mod answers;
+pub mod aoc;
+pub mod bench;
+pub mod cancel;
+pub mod cli;
+pub mod config;
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod error;
+pub mod graph;
+pub mod grid;
+pub mod http;
+pub mod input;
+pub mod json;
+pub mod parser;
+pub mod point;
+pub mod runner;
+pub mod scaffold;
+pub mod search;
+pub mod solution;
+pub mod submit;
+#[cfg(test)]
+mod test_util;
+pub mod watch;
+
+/// Every implemented day. `cargo run -- new <day>` adds new days here.
+pub const DAYS: &[Day] = &[
+    Day::new::<day1::Day1>(1),
+    Day::new::<day2::Day2>(2),
+    Day::new::<day3::Day3>(3),
+    Day::new::<day4::Day4>(4),
+    Day::new::<day5::Day5>(5),
+    Day::new::<day6::Day6>(6),
+    Day::new::<day7::Day7>(7),
+];
@@ -1,48 +1,327 @@
 use std::env;
-mod day1;
-mod day2;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
+use std::path::Path;
+use std::process::exit;
+
+use aoc_2024::config::{self, Config};
+use aoc_2024::input::{self, Source};
+use aoc_2024::solution::{self, Day, Selection};
+use aoc_2024::{answers, aoc, bench, cli, runner, scaffold, submit, watch, DAYS};
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
-
-    if args.len() == 1 {
-        println!("We need a day number.");
-    } else {
-        match args[1].parse::<u32>() {
-            Ok(day) => match day {
-                1 => day1::run(),
-                2 => day2::run(),
-                3 => day3::run(),
-                4 => day4::run(),
-                5 => day5::run(),
-                6 => day6::run(),
-                7 => day7::run(),
-                8 => todo!(),
-                9 => todo!(),
-                10 => todo!(),
-                11 => todo!(),
-                12 => todo!(),
-                13 => todo!(),
-                14 => todo!(),
-                15 => todo!(),
-                16 => todo!(),
-                17 => todo!(),
-                18 => todo!(),
-                19 => todo!(),
-                20 => todo!(),
-                21 => todo!(),
-                22 => todo!(),
-                23 => todo!(),
-                24 => todo!(),
-                25 => todo!(),
-                _ => eprintln!("Invalid day number."),
+    let config = match Config::load() {
+        Ok(config) => config,
+        Err(err) => {
+            eprintln!("{}", err);
+            exit(1);
+        }
+    };
+    let args = match cli::parse(env::args().skip(1), &config) {
+        Ok(args) => args,
+        Err(err) => {
+            eprintln!("{}", err);
+            exit(1);
+        }
+    };
+    config::init(config);
+
+    let Some(command) = args.positional.first() else {
+        println!("We need a day number, or `all` to run every day.");
+        print_implemented();
+        return;
+    };
+
+    match command.as_str() {
+        "all" => {
+            if args.input != Source::Default {
+                eprintln!("--input only works when running a single day.");
+                exit(1);
+            }
+            if !runner::run_all(DAYS, args.selection, args.timeout, args.format) {
+                exit(1);
+            }
+        }
+        "bench" => bench(&args),
+        "verify" => verify(&args),
+        "new" => new(&args),
+        "fetch" => fetch(&args),
+        "submit" => submit(&args),
+        "watch" => watch(&args),
+        number => {
+            let day = find_day(number);
+            let ok = match input::directory(day.number, &args.input) {
+                Some(dir) => {
+                    runner::run_inputs(day, &dir, args.selection, args.timeout, args.format)
+                }
+                None => {
+                    runner::run_day(day, &args.input, args.selection, args.timeout, args.format)
+                }
+            };
+            if !ok {
+                exit(1);
+            }
+        }
+    }
+}
+
+fn bench(args: &cli::Args) {
+    let Some(number) = args.positional.get(1) else {
+        eprintln!("We need a day number to benchmark.");
+        exit(1);
+    };
+    let day = find_day(number);
+
+    let mut options = bench::Options::default();
+    let mut rest = args.positional[2..].iter();
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "--runs" => match rest.next().and_then(|x| x.parse().ok()) {
+                Some(runs) if runs > 0 => options.runs = runs,
+                _ => {
+                    eprintln!("--runs needs a positive number.");
+                    exit(1);
+                }
+            },
+            "--threshold" => match rest.next().and_then(|x| x.parse().ok()) {
+                Some(threshold) => options.threshold = threshold,
+                None => {
+                    eprintln!("--threshold needs a percentage.");
+                    exit(1);
+                }
             },
-            Err(_) => eprintln!("Invalid day number."),
+            "--save" => options.save = true,
+            _ => {
+                eprintln!("Unknown bench option: {}", arg);
+                exit(1);
+            }
+        }
+    }
+
+    // Another input's timings say nothing about the usual one's baseline
+    if args.input != Source::Default {
+        if options.save {
+            eprintln!("--save only works with the day's usual input.");
+            exit(1);
+        }
+        options.compare = false;
+    }
+
+    let input = read_input(day, &args.input);
+    match bench::run(day, &input, args.selection, &options) {
+        Ok(false) => {}
+        Ok(true) => exit(1),
+        Err(err) => {
+            eprintln!("{}", err);
+            exit(1);
+        }
+    }
+}
+
+fn verify(args: &cli::Args) {
+    let path = answers::path();
+    let answers = match answers::load(&path) {
+        Ok(answers) => answers,
+        Err(err) => {
+            eprintln!("Couldn't read {}: {}", path, err);
+            exit(1);
+        }
+    };
+
+    let days = match args.positional.get(1) {
+        Some(number) => std::slice::from_ref(find_day(number)),
+        None if args.input != Source::Default => {
+            eprintln!("--input only works when verifying a single day.");
+            exit(1);
         }
+        None => DAYS,
+    };
+
+    let ok = runner::verify(days, &args.input, args.selection, args.timeout, &answers);
+
+    if !ok {
+        exit(1);
     }
 }
+
+fn new(args: &cli::Args) {
+    let number = day_number(args, "create");
+
+    if solution::find(DAYS, number).is_some() {
+        eprintln!("Day {} is already implemented.", number);
+        exit(1);
+    }
+
+    if let Err(err) = scaffold::new_day(number) {
+        eprintln!("{}", err);
+        exit(1);
+    }
+}
+
+fn fetch(args: &cli::Args) {
+    let number = day_number(args, "fetch");
+    let path = input::path(number);
+
+    match aoc::fetch(number, Path::new(&path), aoc::Client::from_env) {
+        Ok(true) => println!("Downloaded day {} to {}", number, path),
+        Ok(false) => println!("{} is already cached", path),
+        Err(err) => {
+            eprintln!("{}", err);
+            exit(1);
+        }
+    }
+}
+
+fn submit(args: &cli::Args) {
+    let Some(number) = args.positional.get(1) else {
+        eprintln!("We need a day number and part to submit.");
+        exit(1);
+    };
+    let day = find_day(number);
+    let part = match args.positional.get(2).map(String::as_str) {
+        Some("1") => 1,
+        Some("2") => 2,
+        _ => {
+            eprintln!("We need the part (1 or 2) to submit.");
+            exit(1);
+        }
+    };
+
+    let input = read_input(day, &args.input);
+    let answer = match day.solve(&input, Selection::Only(part), args.timeout) {
+        Ok(mut run) => match run.parts.remove(0).answer {
+            Ok(answer) => answer.to_string(),
+            Err(timeout) => {
+                eprintln!("Part {} {}.", part, timeout);
+                exit(1);
+            }
+        },
+        Err(err) => {
+            eprintln!("{}", err);
+            exit(1);
+        }
+    };
+
+    let history_path = submit::history_path();
+    let mut history = match submit::History::load(&history_path) {
+        Ok(history) => history,
+        Err(err) => {
+            eprintln!("Couldn't read {}: {}", history_path, err);
+            exit(1);
+        }
+    };
+    if let Err(reason) = history.check(day.number, part, &answer, submit::now()) {
+        eprintln!("Not submitting {}: {}", answer, reason);
+        exit(1);
+    }
+
+    println!("Submitting {} for day {} part {}", answer, day.number, part);
+    let outcome = match aoc::Client::from_env().and_then(|c| c.submit(day.number, part, &answer)) {
+        Ok(outcome) => outcome,
+        Err(err) => {
+            eprintln!("{}", err);
+            exit(1);
+        }
+    };
+
+    history.record(day.number, part, &answer, &outcome, submit::now());
+    if let Err(err) = history.save(&history_path) {
+        eprintln!("Couldn't update {}: {}", history_path, err);
+    }
+
+    match outcome {
+        submit::Outcome::Right => println!("That's the right answer!"),
+        submit::Outcome::Wrong(hint, wait) => {
+            let hint = match hint {
+                Some(submit::Hint::TooHigh) => " (too high)",
+                Some(submit::Hint::TooLow) => " (too low)",
+                None => "",
+            };
+            println!(
+                "Wrong answer{}. Wait {}s before trying again.",
+                hint,
+                wait.as_secs()
+            );
+            exit(1);
+        }
+        submit::Outcome::TooSoon(wait) => {
+            println!("Too soon, wait another {}s.", wait.as_secs());
+            exit(1);
+        }
+        submit::Outcome::AlreadySolved => println!("That part is already solved."),
+        submit::Outcome::Unknown(text) => {
+            println!("Couldn't make sense of the response:\n{}", text);
+            exit(1);
+        }
+    }
+}
+
+fn watch(args: &cli::Args) {
+    let Some(number) = args.positional.get(1) else {
+        eprintln!("We need a day number to watch.");
+        exit(1);
+    };
+    let day = find_day(number);
+
+    let path = match &args.input {
+        Source::Default => input::path(day.number),
+        Source::Path(path) => path.clone(),
+        Source::Stdin => {
+            eprintln!("Can't watch stdin, pass a file to --input instead.");
+            exit(1);
+        }
+    };
+
+    watch::watch(
+        day,
+        Path::new(&path),
+        args.selection,
+        args.timeout,
+        args.format,
+    );
+}
+
+/// The day number a subcommand acts on, which doesn't have to be implemented.
+fn day_number(args: &cli::Args, action: &str) -> u32 {
+    match args.positional.get(1).map(|x| x.parse::<u32>()) {
+        Some(Ok(number)) if (1..=25).contains(&number) => number,
+        _ => {
+            eprintln!("We need a day number between 1 and 25 to {}.", action);
+            exit(1);
+        }
+    }
+}
+
+/// Read a day's input, exiting if it can't be read.
+fn read_input(day: &Day, source: &Source) -> String {
+    match input::load(day.number, source) {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("Couldn't read {}: {}", input::name(day.number, source), err);
+            exit(1);
+        }
+    }
+}
+
+/// Look up a day from a command line argument, exiting if it's not available.
+fn find_day(arg: &str) -> &'static Day {
+    let number = match arg.parse::<u32>() {
+        Ok(number) if (1..=25).contains(&number) => number,
+        _ => {
+            eprintln!("Invalid day number.");
+            exit(1);
+        }
+    };
+
+    match solution::find(DAYS, number) {
+        Some(day) => day,
+        None => {
+            eprintln!("Day {} isn't implemented yet.", number);
+            print_implemented();
+            exit(1);
+        }
+    }
+}
+
+fn print_implemented() {
+    let days: Vec<String> = DAYS.iter().map(|day| day.number.to_string()).collect();
+    println!("Implemented days: {}", days.join(", "));
+}
@@ -0,0 +1,383 @@
+use std::marker::PhantomData;
+use std::str::{self, FromStr};
+
+use crate::error::ParseError;
+
+/// What a parser gives back: its value and the input left after it, or the
+/// input where it stopped making sense.
+pub type ParseResult<I, O> = Result<(O, I), I>;
+
+/// Text the parsers can work through without copying it.
+pub trait Input: Copy {
+    fn as_bytes(&self) -> &[u8];
+
+    /// Split off the first `n` bytes. Parsers only split after ASCII, so a
+    /// `&str` is always split on a character boundary.
+    fn split_at(self, n: usize) -> (Self, Self);
+}
+
+impl Input for &str {
+    fn as_bytes(&self) -> &[u8] {
+        str::as_bytes(self)
+    }
+
+    fn split_at(self, n: usize) -> (Self, Self) {
+        str::split_at(self, n)
+    }
+}
+
+impl Input for &[u8] {
+    fn as_bytes(&self) -> &[u8] {
+        self
+    }
+
+    fn split_at(self, n: usize) -> (Self, Self) {
+        <[u8]>::split_at(self, n)
+    }
+}
+
+pub trait Parser<I> {
+    type Output;
+
+    fn parse(&self, input: I) -> ParseResult<I, Self::Output>;
+}
+
+/// Run `parser` over all of `input`, failing where it stops if anything is
+/// left over.
+pub fn parse_all<I: Input, P: Parser<I>>(parser: &P, input: I) -> Result<P::Output, I> {
+    match parser.parse(input)? {
+        (output, rest) if rest.as_bytes().is_empty() => Ok(output),
+        (_, rest) => Err(rest),
+    }
+}
+
+/// Parse all of one line of a day's input, pointing the error at wherever
+/// the parser gave up.
+pub fn parse_line<'a, P: Parser<&'a str>>(
+    day: u32,
+    index: usize,
+    line: &'a str,
+    parser: &P,
+    message: &str,
+) -> Result<P::Output, ParseError> {
+    parse_all(parser, line)
+        .map_err(|rest| ParseError::new(day, index, line, line.len() - rest.len(), message))
+}
+
+/// Exactly the given text. Gives back the matched part of the input.
+pub fn literal(text: &'static str) -> Literal {
+    Literal(text)
+}
+
+pub struct Literal(&'static str);
+
+impl<I: Input> Parser<I> for Literal {
+    type Output = I;
+
+    fn parse(&self, input: I) -> ParseResult<I, I> {
+        match input.as_bytes().starts_with(self.0.as_bytes()) {
+            true => Ok(input.split_at(self.0.len())),
+            false => Err(input),
+        }
+    }
+}
+
+/// One or more spaces or tabs.
+pub fn spaces() -> Spaces {
+    Spaces
+}
+
+pub struct Spaces;
+
+impl<I: Input> Parser<I> for Spaces {
+    type Output = I;
+
+    fn parse(&self, input: I) -> ParseResult<I, I> {
+        let count = input
+            .as_bytes()
+            .iter()
+            .take_while(|&&b| b == b' ' || b == b'\t')
+            .count();
+        match count {
+            0 => Err(input),
+            count => Ok(input.split_at(count)),
+        }
+    }
+}
+
+/// A run of digits, as any number type. Fails if it doesn't fit.
+pub fn unsigned<T: FromStr>() -> Unsigned<T> {
+    Unsigned(PhantomData)
+}
+
+pub struct Unsigned<T>(PhantomData<T>);
+
+impl<I: Input, T: FromStr> Parser<I> for Unsigned<T> {
+    type Output = T;
+
+    fn parse(&self, input: I) -> ParseResult<I, T> {
+        number(input, 0, usize::MAX)
+    }
+}
+
+/// Digits with an optional `-` or `+` in front.
+pub fn signed<T: FromStr>() -> Signed<T> {
+    Signed(PhantomData)
+}
+
+pub struct Signed<T>(PhantomData<T>);
+
+impl<I: Input, T: FromStr> Parser<I> for Signed<T> {
+    type Output = T;
+
+    fn parse(&self, input: I) -> ParseResult<I, T> {
+        let sign = match input.as_bytes().first() {
+            Some(b'-' | b'+') => 1,
+            _ => 0,
+        };
+        number(input, sign, usize::MAX)
+    }
+}
+
+/// Between `min` and `max` digits, taking as many as it can. A number always
+/// has at least one digit, so a `min` of 0 means the same as 1.
+pub fn bounded<T: FromStr>(min: usize, max: usize) -> Bounded<T> {
+    Bounded(min.max(1), max, PhantomData)
+}
+
+pub struct Bounded<T>(usize, usize, PhantomData<T>);
+
+impl<I: Input, T: FromStr> Parser<I> for Bounded<T> {
+    type Output = T;
+
+    fn parse(&self, input: I) -> ParseResult<I, T> {
+        match number(input, 0, self.1) {
+            Ok((_, rest)) if input.as_bytes().len() - rest.as_bytes().len() < self.0 => Err(input),
+            result => result,
+        }
+    }
+}
+
+/// Parse the `sign` bytes at the start of `input` and up to `max` digits
+/// after them as a number.
+fn number<I: Input, T: FromStr>(input: I, sign: usize, max: usize) -> ParseResult<I, T> {
+    let digits = input.as_bytes()[sign..]
+        .iter()
+        .take(max)
+        .take_while(|b| b.is_ascii_digit())
+        .count();
+    if digits == 0 {
+        return Err(input);
+    }
+
+    let (text, rest) = input.split_at(sign + digits);
+    match str::from_utf8(text.as_bytes()).map(str::parse) {
+        Ok(Ok(number)) => Ok((number, rest)),
+        _ => Err(input),
+    }
+}
+
+/// Turn what `parser` finds into something else.
+pub fn map<P, F>(parser: P, f: F) -> Map<P, F> {
+    Map(parser, f)
+}
+
+pub struct Map<P, F>(P, F);
+
+impl<I, P: Parser<I>, F: Fn(P::Output) -> U, U> Parser<I> for Map<P, F> {
+    type Output = U;
+
+    fn parse(&self, input: I) -> ParseResult<I, U> {
+        let (output, rest) = self.0.parse(input)?;
+        Ok(((self.1)(output), rest))
+    }
+}
+
+// A tuple of parsers runs them one after another
+macro_rules! sequence {
+    ($($parser:ident $output:ident),+) => {
+        impl<I, $($parser: Parser<I>),+> Parser<I> for ($($parser,)+) {
+            type Output = ($($parser::Output,)+);
+
+            #[allow(non_snake_case)]
+            fn parse(&self, input: I) -> ParseResult<I, Self::Output> {
+                let ($($parser,)+) = self;
+                let rest = input;
+                $(let ($output, rest) = $parser.parse(rest)?;)+
+                Ok((($($output,)+), rest))
+            }
+        }
+    };
+}
+
+sequence!(A a, B b);
+sequence!(A a, B b, C c);
+sequence!(A a, B b, C c, D d);
+sequence!(A a, B b, C c, D d, E e);
+
+/// The first of a tuple of parsers that matches. If none do, the error is
+/// from whichever got furthest.
+pub fn alt<T>(parsers: T) -> Alt<T> {
+    Alt(parsers)
+}
+
+pub struct Alt<T>(T);
+
+macro_rules! alternation {
+    ($first:ident $(, $parser:ident)+) => {
+        impl<I: Input, $first: Parser<I>, $($parser: Parser<I, Output = $first::Output>),+>
+            Parser<I> for Alt<($first, $($parser),+)>
+        {
+            type Output = $first::Output;
+
+            #[allow(non_snake_case)]
+            fn parse(&self, input: I) -> ParseResult<I, Self::Output> {
+                let ($first, $($parser),+) = &self.0;
+                let mut furthest = match $first.parse(input) {
+                    Ok(found) => return Ok(found),
+                    Err(at) => at,
+                };
+                $(
+                    match $parser.parse(input) {
+                        Ok(found) => return Ok(found),
+                        Err(at) if at.as_bytes().len() < furthest.as_bytes().len() => furthest = at,
+                        Err(_) => {}
+                    }
+                )+
+                Err(furthest)
+            }
+        }
+    };
+}
+
+alternation!(A, B);
+alternation!(A, B, C);
+alternation!(A, B, C, D);
+
+/// Zero or more of `parser`, as many as it matches.
+pub fn many<P>(parser: P) -> Many<P> {
+    Many(parser)
+}
+
+pub struct Many<P>(P);
+
+impl<I: Input, P: Parser<I>> Parser<I> for Many<P> {
+    type Output = Vec<P::Output>;
+
+    fn parse(&self, input: I) -> ParseResult<I, Self::Output> {
+        let mut outputs = vec![];
+        let mut rest = input;
+        while let Ok((output, next)) = self.0.parse(rest) {
+            if next.as_bytes().len() == rest.as_bytes().len() {
+                break; // Matching nothing forever isn't progress
+            }
+            outputs.push(output);
+            rest = next;
+        }
+        Ok((outputs, rest))
+    }
+}
+
+/// One or more of `parser` with `separator` between them. A separator that
+/// isn't followed by another match is left in the rest of the input.
+pub fn separated<P, S>(parser: P, separator: S) -> Separated<P, S> {
+    Separated(parser, separator)
+}
+
+pub struct Separated<P, S>(P, S);
+
+impl<I: Input, P: Parser<I>, S: Parser<I>> Parser<I> for Separated<P, S> {
+    type Output = Vec<P::Output>;
+
+    fn parse(&self, input: I) -> ParseResult<I, Self::Output> {
+        let (first, mut rest) = self.0.parse(input)?;
+        let mut outputs = vec![first];
+        while let Ok((_, after)) = self.1.parse(rest) {
+            let Ok((output, next)) = self.0.parse(after) else {
+                break;
+            };
+            outputs.push(output);
+            rest = next;
+        }
+        Ok((outputs, rest))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_literal() {
+        assert_eq!(literal("mul(").parse("mul(2,4)"), Ok(("mul(", "2,4)")));
+        assert_eq!(literal("mul(").parse("mul[2,4]"), Err("mul[2,4]"));
+        assert_eq!(
+            literal("ab").parse(&b"abc"[..]),
+            Ok((&b"ab"[..], &b"c"[..]))
+        );
+        assert_eq!(spaces().parse("  \t4"), Ok(("  \t", "4")));
+        assert_eq!(spaces().parse("4"), Err("4"));
+    }
+
+    #[test]
+    fn test_numbers() {
+        assert_eq!(unsigned::<u32>().parse("123abc"), Ok((123, "abc")));
+        assert_eq!(unsigned::<u32>().parse("-1"), Err("-1"));
+        assert_eq!(unsigned::<u8>().parse("256"), Err("256"));
+        assert_eq!(signed::<i32>().parse("-12,"), Ok((-12, ",")));
+        assert_eq!(signed::<i32>().parse("+7"), Ok((7, "")));
+        assert_eq!(signed::<i32>().parse("-x"), Err("-x"));
+        assert_eq!(unsigned::<u64>().parse(&b"42"[..]), Ok((42, &b""[..])));
+
+        assert_eq!(bounded::<u32>(1, 3).parse("12345"), Ok((123, "45")));
+        assert_eq!(bounded::<u32>(2, 3).parse("1,"), Err("1,"));
+        assert_eq!(bounded::<u32>(0, 3).parse(","), Err(","));
+    }
+
+    #[test]
+    fn test_sequence_and_alt() {
+        let pair = map(
+            (unsigned(), literal(","), unsigned()),
+            |(x, _, y): (u32, &str, u32)| x * y,
+        );
+        assert_eq!(pair.parse("3,4)"), Ok((12, ")")));
+        assert_eq!(pair.parse("3;4"), Err(";4"));
+
+        let switch = alt((
+            map(literal("do()"), |_| true),
+            map(literal("don't()"), |_| false),
+        ));
+        assert_eq!(switch.parse("don't()!"), Ok((false, "!")));
+        assert_eq!(switch.parse("do!"), Err("do!"));
+
+        // The error comes from the alternative that got furthest
+        let either = alt((pair, map(unsigned(), |x: u32| x + 1)));
+        assert_eq!(either.parse("7"), Ok((8, "")));
+        assert_eq!(either.parse("x"), Err("x"));
+        assert_eq!(either.parse("7,x").map(|(n, _)| n), Ok(8));
+    }
+
+    #[test]
+    fn test_repetition() {
+        let list = separated(unsigned::<u32>(), literal(","));
+        assert_eq!(list.parse("1,2,3"), Ok((vec![1, 2, 3], "")));
+        assert_eq!(list.parse("1,2;3"), Ok((vec![1, 2], ";3")));
+        assert_eq!(list.parse("1,2,x"), Ok((vec![1, 2], ",x")));
+        assert_eq!(list.parse("1,2,"), Ok((vec![1, 2], ",")));
+        assert_eq!(list.parse(",1"), Err(",1"));
+
+        let letters = many(alt((literal("a"), literal("b"))));
+        assert_eq!(letters.parse("abbac"), Ok((vec!["a", "b", "b", "a"], "c")));
+        assert_eq!(letters.parse("c"), Ok((vec![], "c")));
+    }
+
+    #[test]
+    fn test_parse_line() {
+        let numbers = (signed::<i32>(), spaces(), signed::<i32>());
+        assert_eq!(parse_all(&numbers, "3   4"), Ok((3, "   ", 4)));
+        assert_eq!(parse_all(&numbers, "3   4 5"), Err(" 5"));
+
+        let err = parse_line(1, 1, "4   x", &numbers, "expected two numbers").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 5));
+    }
+}
@@ -0,0 +1,230 @@
+use std::ops::{Add, AddAssign, Mul, Neg, Sub};
+
+/// A position or offset on an unbounded plane, with `y` growing downwards
+/// like the rows of a puzzle input.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point { x: 0, y: 0 };
+
+    pub const fn new(x: i64, y: i64) -> Self {
+        Point { x, y }
+    }
+
+    /// The point `n` steps away in `direction`.
+    pub fn step(self, direction: impl Into<Point>, n: i64) -> Self {
+        self + direction.into() * n
+    }
+
+    /// The distance to `other` moving only horizontally and vertically.
+    pub fn manhattan(self, other: Point) -> u64 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    /// The distance to `other` when diagonal moves count as one step.
+    pub fn chebyshev(self, other: Point) -> u64 {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+
+    /// The point as `(x, y)` grid indices, unless it's left of or above the
+    /// origin. Use `Grid::index_of` to check the other edges too.
+    pub fn to_index(self) -> Option<(usize, usize)> {
+        Some((self.x.try_into().ok()?, self.y.try_into().ok()?))
+    }
+}
+
+impl From<(usize, usize)> for Point {
+    fn from((x, y): (usize, usize)) -> Self {
+        Point::new(x as i64, y as i64)
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, other: Point) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, other: Point) -> Point {
+        Point::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Point {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+impl Mul<i64> for Point {
+    type Output = Point;
+
+    fn mul(self, n: i64) -> Point {
+        Point::new(self.x * n, self.y * n)
+    }
+}
+
+/// One of the four directions along the grid lines.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Dir4 {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Dir4 {
+    /// Every direction, clockwise from up.
+    pub const ALL: [Dir4; 4] = [Dir4::Up, Dir4::Right, Dir4::Down, Dir4::Left];
+
+    /// A quarter turn clockwise.
+    pub fn turn_right(self) -> Self {
+        Dir4::ALL[(self as usize + 1) % 4]
+    }
+
+    /// A quarter turn anticlockwise.
+    pub fn turn_left(self) -> Self {
+        Dir4::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn turn_around(self) -> Self {
+        Dir4::ALL[(self as usize + 2) % 4]
+    }
+}
+
+impl From<Dir4> for Point {
+    fn from(direction: Dir4) -> Self {
+        match direction {
+            Dir4::Up => Point::new(0, -1),
+            Dir4::Right => Point::new(1, 0),
+            Dir4::Down => Point::new(0, 1),
+            Dir4::Left => Point::new(-1, 0),
+        }
+    }
+}
+
+/// One of the eight directions to a neighbouring cell, diagonals included.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Dir8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Dir8 {
+    /// Every direction, clockwise from up.
+    pub const ALL: [Dir8; 8] = [
+        Dir8::Up,
+        Dir8::UpRight,
+        Dir8::Right,
+        Dir8::DownRight,
+        Dir8::Down,
+        Dir8::DownLeft,
+        Dir8::Left,
+        Dir8::UpLeft,
+    ];
+
+    /// An eighth of a turn clockwise, so up turns to up-right.
+    pub fn turn_right(self) -> Self {
+        Dir8::ALL[(self as usize + 1) % 8]
+    }
+
+    /// An eighth of a turn anticlockwise, so up turns to up-left.
+    pub fn turn_left(self) -> Self {
+        Dir8::ALL[(self as usize + 7) % 8]
+    }
+
+    pub fn turn_around(self) -> Self {
+        Dir8::ALL[(self as usize + 4) % 8]
+    }
+}
+
+impl From<Dir8> for Point {
+    fn from(direction: Dir8) -> Self {
+        match direction {
+            Dir8::Up => Point::new(0, -1),
+            Dir8::UpRight => Point::new(1, -1),
+            Dir8::Right => Point::new(1, 0),
+            Dir8::DownRight => Point::new(1, 1),
+            Dir8::Down => Point::new(0, 1),
+            Dir8::DownLeft => Point::new(-1, 1),
+            Dir8::Left => Point::new(-1, 0),
+            Dir8::UpLeft => Point::new(-1, -1),
+        }
+    }
+}
+
+impl From<Dir4> for Dir8 {
+    fn from(direction: Dir4) -> Self {
+        Dir8::ALL[direction as usize * 2]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_turns() {
+        assert_eq!(Dir4::Up.turn_right(), Dir4::Right);
+        assert_eq!(Dir4::Up.turn_left(), Dir4::Left);
+        assert_eq!(Dir4::Left.turn_around(), Dir4::Right);
+        assert_eq!(Dir8::Up.turn_right(), Dir8::UpRight);
+        assert_eq!(Dir8::Up.turn_left(), Dir8::UpLeft);
+        assert_eq!(Dir8::DownLeft.turn_around(), Dir8::UpRight);
+
+        for direction in Dir8::ALL {
+            let back = Point::from(direction.turn_around());
+            assert_eq!(back, -Point::from(direction));
+        }
+        for direction in Dir4::ALL {
+            assert_eq!(Point::from(direction), Point::from(Dir8::from(direction)));
+        }
+    }
+
+    #[test]
+    fn test_step() {
+        let start = Point::new(2, 3);
+        assert_eq!(start.step(Dir4::Up, 1), Point::new(2, 2));
+        assert_eq!(start.step(Dir8::DownLeft, 3), Point::new(-1, 6));
+        assert_eq!(start.step(Dir4::Right, -2), Point::new(0, 3));
+    }
+
+    #[test]
+    fn test_distances() {
+        let (a, b) = (Point::new(1, -2), Point::new(-3, 4));
+        assert_eq!(a.manhattan(b), 10);
+        assert_eq!(a.chebyshev(b), 6);
+        assert_eq!(a.manhattan(a), 0);
+    }
+
+    #[test]
+    fn test_to_index() {
+        assert_eq!(Point::new(3, 0).to_index(), Some((3, 0)));
+        assert_eq!(Point::new(-1, 0).to_index(), None);
+        assert_eq!(Point::new(0, -1).to_index(), None);
+        assert_eq!(Point::from((4, 5)), Point::new(4, 5));
+    }
+}
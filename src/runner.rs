@@ -0,0 +1,804 @@
+use std::any::Any;
+use std::cell::Cell;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, Once};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::answer::Answer;
+use crate::answers::{self, Answers};
+use crate::error::ParseError;
+use crate::input::{self, Source};
+use crate::json::Value;
+use crate::solution::{Day, Part, Run, Selection};
+
+/// How results are printed.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+}
+
+impl Format {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "text" => Some(Format::Text),
+            "json" => Some(Format::Json),
+            _ => None,
+        }
+    }
+}
+
+/// Why a day couldn't be run.
+pub enum Failure {
+    /// The input couldn't be read
+    Input(String),
+    Parse(ParseError),
+    /// The solver panicked, with the panic message
+    Panic(String),
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Failure::Input(message) => write!(f, "{}", message),
+            Failure::Parse(err) => write!(f, "{}", err),
+            Failure::Panic(message) => write!(f, "panicked: {}", message),
+        }
+    }
+}
+
+/// What happened when running a day.
+pub struct Report {
+    pub day: u32,
+    pub result: Result<Run, Failure>,
+}
+
+impl Report {
+    /// Whether the input couldn't be read. For a day's default input that
+    /// just means it hasn't been fetched yet.
+    fn missing_input(&self) -> bool {
+        matches!(self.result, Err(Failure::Input(_)))
+    }
+
+    /// Whether the day ran and every part finished, or its input is missing
+    /// and `skip_missing` says that's fine.
+    fn ok(&self, skip_missing: bool) -> bool {
+        let finished = self.result.as_ref().is_ok_and(|run| !run.timed_out());
+        finished || (skip_missing && self.missing_input())
+    }
+}
+
+/// Load a day's input and run the selected parts, giving each part
+/// `timeout` to finish.
+pub fn run(day: &Day, source: &Source, selection: Selection, timeout: Option<Duration>) -> Report {
+    let result = match input::load(day.number, source) {
+        Ok(input) => day
+            .solve(&input, selection, timeout)
+            .map_err(Failure::Parse),
+        Err(err) => Err(Failure::Input(format!(
+            "Couldn't read {}: {}",
+            input::name(day.number, source),
+            err
+        ))),
+    };
+
+    Report {
+        day: day.number,
+        result,
+    }
+}
+
+/// Run a single day and print its answers, returning whether every part
+/// finished.
+pub fn run_day(
+    day: &Day,
+    source: &Source,
+    selection: Selection,
+    timeout: Option<Duration>,
+    format: Format,
+) -> bool {
+    let report = run(day, source, selection, timeout);
+
+    match format {
+        Format::Json => println!("{}", day_json(&report, false)),
+        Format::Text => match &report.result {
+            Ok(run) => {
+                println!("Parse: {:.2?}", run.parse);
+                for part in &run.parts {
+                    println!("Part {}: {} ({:.2?})", part.number, answer(part), part.time);
+                }
+            }
+            Err(err) => eprintln!("{}", err),
+        },
+    }
+
+    report.ok(false)
+}
+
+/// A part's answer, or what stopped it from getting one.
+fn answer(part: &Part) -> String {
+    match &part.answer {
+        Ok(answer) => answer.to_string(),
+        Err(timeout) => timeout.to_string().to_uppercase(),
+    }
+}
+
+/// Run every registered day on a pool of threads and print a summary in day
+/// order, returning whether every day with an input ran cleanly.
+pub fn run_all(
+    days: &[Day],
+    selection: Selection,
+    timeout: Option<Duration>,
+    format: Format,
+) -> bool {
+    let start = Instant::now();
+    let outcomes = run_parallel(days, |day| run_default(day, selection, timeout));
+    let total = start.elapsed();
+
+    let ok = outcomes.iter().all(Outcome::ok);
+
+    match format {
+        Format::Json => {
+            let days = outcomes.iter().map(Outcome::to_json).collect();
+            let summary = Value::Object(vec![
+                ("days", Value::Array(days)),
+                ("total_ns", total.as_nanos().into()),
+                ("ok", ok.into()),
+            ]);
+            println!("{}", summary);
+        }
+        Format::Text => print_table(&outcomes, total),
+    }
+
+    ok
+}
+
+/// What `all` got for one day: a report on its input, or a check of every
+/// input when its data path is a directory.
+enum Outcome {
+    Single(Report),
+    Inputs {
+        day: u32,
+        checked: Result<(Checked, bool), String>,
+    },
+}
+
+impl Outcome {
+    fn ok(&self) -> bool {
+        match self {
+            // A missing input just means that day hasn't been fetched yet
+            Outcome::Single(report) => report.ok(true),
+            Outcome::Inputs { checked, .. } => checked.as_ref().is_ok_and(|&(_, ok)| ok),
+        }
+    }
+
+    fn to_json(&self) -> Value {
+        match self {
+            Outcome::Single(report) => day_json(report, true),
+            Outcome::Inputs {
+                day,
+                checked: Ok((results, ok)),
+            } => inputs_json(*day, results, *ok),
+            Outcome::Inputs {
+                day,
+                checked: Err(err),
+            } => Value::Object(vec![
+                ("day", (*day).into()),
+                ("ok", false.into()),
+                ("skipped", false.into()),
+                ("error", err.clone().into()),
+            ]),
+        }
+    }
+}
+
+/// Run a day on its default input, or on every input in its directory.
+fn run_default(day: &Day, selection: Selection, timeout: Option<Duration>) -> Outcome {
+    match input::directory(day.number, &Source::Default) {
+        Some(dir) => Outcome::Inputs {
+            day: day.number,
+            checked: check_inputs(day, &dir, selection, timeout),
+        },
+        None => Outcome::Single(run(day, &Source::Default, selection, timeout)),
+    }
+}
+
+/// Run `job` for each day on a worker thread, catching panics so one broken
+/// day doesn't take the rest down with it.
+fn run_parallel(days: &[Day], job: impl Fn(&Day) -> Outcome + Sync) -> Vec<Outcome> {
+    let workers = thread::available_parallelism()
+        .map_or(1, |n| n.get())
+        .min(days.len());
+    let next = AtomicUsize::new(0);
+    let outcomes: Vec<Mutex<Option<Outcome>>> = days.iter().map(|_| Mutex::new(None)).collect();
+
+    silence_worker_panics();
+
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| {
+                QUIET.with(|quiet| quiet.set(true));
+
+                loop {
+                    let i = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(day) = days.get(i) else {
+                        break;
+                    };
+
+                    let outcome = panic::catch_unwind(AssertUnwindSafe(|| job(day)))
+                        .unwrap_or_else(|payload| {
+                            Outcome::Single(Report {
+                                day: day.number,
+                                result: Err(Failure::Panic(panic_message(payload))),
+                            })
+                        });
+                    *outcomes[i].lock().unwrap() = Some(outcome);
+                }
+            });
+        }
+    });
+
+    outcomes
+        .into_iter()
+        .map(|outcome| outcome.into_inner().unwrap().unwrap())
+        .collect()
+}
+
+thread_local! {
+    /// Set on worker threads, whose panics are reported in the results
+    static QUIET: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Keep the panic hook from printing panics on worker threads over the top of
+/// the results. Panics anywhere else still go to the hook that was there
+/// before, and the hook is only ever installed once.
+fn silence_worker_panics() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !QUIET.with(Cell::get) {
+                previous(info);
+            }
+        }));
+    });
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => "unknown panic".to_string(),
+        },
+    }
+}
+
+fn print_table(outcomes: &[Outcome], total: Duration) {
+    println!(
+        "{:>3}  {:>4}  {:<20}  {:>12}  {:>12}",
+        "Day", "Part", "Answer", "Parse", "Solve"
+    );
+    for outcome in outcomes {
+        match outcome {
+            Outcome::Single(report) => print_rows(report, None),
+            Outcome::Inputs {
+                day,
+                checked: Ok((results, _)),
+            } => {
+                for (file, report, expected) in results {
+                    println!("{:>3}  {:>4}  {}:", day, "-", file_name(file));
+                    print_rows(report, Some(expected));
+                }
+            }
+            Outcome::Inputs {
+                day,
+                checked: Err(err),
+            } => println!("{:>3}  {:>4}  {}", day, "-", err),
+        }
+    }
+
+    println!();
+    println!("Total: {:.2?}", total);
+}
+
+/// Print a report's rows in the summary table, followed by how each part
+/// compares with `expected` when there are expected answers.
+fn print_rows(report: &Report, expected: Option<&Answers>) {
+    let run = match &report.result {
+        Ok(run) => run,
+        Err(Failure::Input(err)) => {
+            println!("{:>3}  {:>4}  {}", report.day, "-", err);
+            return;
+        }
+        Err(Failure::Parse(err)) => {
+            println!("{:>3}  {:>4}  couldn't parse input", report.day, "-");
+            println!("{}", err);
+            return;
+        }
+        Err(Failure::Panic(message)) => {
+            println!("{:>3}  {:>4}  FAILED: {}", report.day, "-", message);
+            return;
+        }
+    };
+
+    for (i, part) in run.parts.iter().enumerate() {
+        // Parsing is shared by both parts, so only show it once
+        let parse = match i {
+            0 => format!("{:.2?}", run.parse),
+            _ => String::new(),
+        };
+        let status = match expected {
+            Some(answers) => format!(
+                "  {}",
+                status(&check(part, answers.get(&(report.day, part.number))))
+            ),
+            None => String::new(),
+        };
+        println!(
+            "{:>3}  {:>4}  {:<20}  {:>12}  {:>12}{}",
+            report.day,
+            part.number,
+            answer(part),
+            parse,
+            format!("{:.2?}", part.time),
+            status
+        );
+    }
+}
+
+fn day_json(report: &Report, skip_missing: bool) -> Value {
+    Value::Object(day_fields(report, None, skip_missing))
+}
+
+/// The JSON fields describing a report, with each part checked against
+/// `expected` when there are expected answers. With `skip_missing`, a day
+/// whose input is missing is marked as skipped rather than failed.
+fn day_fields(
+    report: &Report,
+    expected: Option<&Answers>,
+    skip_missing: bool,
+) -> Vec<(&'static str, Value)> {
+    let mut ok = report.ok(skip_missing);
+    let skipped = skip_missing && report.missing_input();
+
+    let (parse, parts, error) = match &report.result {
+        Ok(run) => {
+            let parts = run
+                .parts
+                .iter()
+                .map(|part| {
+                    let mut fields = vec![
+                        ("part", part.number.into()),
+                        ("answer", part.answer.as_ref().ok().into()),
+                        ("timed_out", part.answer.is_err().into()),
+                        ("time_ns", part.time.as_nanos().into()),
+                    ];
+                    if let Some(answers) = expected {
+                        let wanted = answers.get(&(report.day, part.number));
+                        let check = check(part, wanted);
+                        ok &= check.ok();
+                        fields.push(("expected", wanted.into()));
+                        fields.push(("check", check.name().into()));
+                    }
+                    Value::Object(fields)
+                })
+                .collect();
+            (Some(run.parse.as_nanos()), parts, None)
+        }
+        Err(err) => (None, vec![], Some(err.to_string())),
+    };
+
+    vec![
+        ("day", report.day.into()),
+        ("ok", ok.into()),
+        ("skipped", skipped.into()),
+        ("error", error.into()),
+        ("parse_ns", parse.into()),
+        ("parts", Value::Array(parts)),
+    ]
+}
+
+/// How a part's answer compares with the expected one.
+#[derive(Debug, PartialEq)]
+enum Check<'a> {
+    Pass,
+    Fail(&'a Answer),
+    /// There's no expected answer to compare with
+    Missing,
+    TimedOut,
+}
+
+impl Check<'_> {
+    fn ok(&self) -> bool {
+        matches!(self, Check::Pass | Check::Missing)
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Check::Pass => "pass",
+            Check::Fail(_) => "fail",
+            Check::Missing => "missing",
+            Check::TimedOut => "timed_out",
+        }
+    }
+}
+
+/// Whether a report ran and every part matched or had nothing to match.
+fn passed(report: &Report, expected: &Answers) -> bool {
+    report.result.as_ref().is_ok_and(|run| {
+        run.parts
+            .iter()
+            .all(|part| check(part, expected.get(&(report.day, part.number))).ok())
+    })
+}
+
+/// How a check is shown next to an input's answer.
+fn status(check: &Check) -> String {
+    match check {
+        Check::Pass => "PASS".to_string(),
+        Check::Fail(expected) => format!("FAIL (expected {})", expected),
+        Check::Missing => "-".to_string(),
+        Check::TimedOut => "FAIL".to_string(),
+    }
+}
+
+fn check<'a>(part: &Part, expected: Option<&'a Answer>) -> Check<'a> {
+    match (&part.answer, expected) {
+        (Err(_), _) => Check::TimedOut,
+        (Ok(answer), Some(expected)) if expected == answer => Check::Pass,
+        (Ok(_), Some(expected)) => Check::Fail(expected),
+        (Ok(_), None) => Check::Missing,
+    }
+}
+
+/// Run a day on every input in `dir` and print how each one compares with
+/// the answers in its `.answers` file. Returns whether every input ran and
+/// matched what was expected.
+pub fn run_inputs(
+    day: &Day,
+    dir: &Path,
+    selection: Selection,
+    timeout: Option<Duration>,
+    format: Format,
+) -> bool {
+    let (results, ok) = match check_inputs(day, dir, selection, timeout) {
+        Ok(checked) => checked,
+        Err(err) => {
+            eprintln!("{}", err);
+            return false;
+        }
+    };
+
+    match format {
+        Format::Json => println!("{}", inputs_json(day.number, &results, ok)),
+        Format::Text => print_inputs(&results),
+    }
+
+    ok
+}
+
+/// Each input in a directory with its report and the answers it should give.
+type Checked = Vec<(PathBuf, Report, Answers)>;
+
+/// Run a day on every input in `dir`, checking each one against the answers
+/// in its `.answers` file if it has one. Returns the results and whether every
+/// input ran and matched what was expected, or why the inputs couldn't be
+/// listed.
+fn check_inputs(
+    day: &Day,
+    dir: &Path,
+    selection: Selection,
+    timeout: Option<Duration>,
+) -> Result<(Checked, bool), String> {
+    let files = match input::files(dir) {
+        Ok(files) if !files.is_empty() => files,
+        Ok(_) => return Err(format!("There are no inputs in {}", dir.display())),
+        Err(err) => return Err(format!("Couldn't read {}: {}", dir.display(), err)),
+    };
+
+    let mut ok = true;
+    let mut results = vec![];
+    for file in files {
+        let report = run(
+            day,
+            &Source::Path(file.display().to_string()),
+            selection,
+            timeout,
+        );
+
+        let answers_path = input::answers_path(&file);
+        let expected = match answers::load(&answers_path.display().to_string()) {
+            Ok(expected) => expected,
+            Err(err) => {
+                eprintln!("Couldn't read {}: {}", answers_path.display(), err);
+                ok = false;
+                Answers::new()
+            }
+        };
+
+        ok &= passed(&report, &expected);
+        results.push((file, report, expected));
+    }
+
+    Ok((results, ok))
+}
+
+fn inputs_json(day: u32, results: &Checked, ok: bool) -> Value {
+    let inputs = results
+        .iter()
+        .map(|(file, report, expected)| {
+            let mut fields = vec![("input", file.display().to_string().into())];
+            fields.extend(day_fields(report, Some(expected), false));
+            Value::Object(fields)
+        })
+        .collect();
+    Value::Object(vec![
+        ("day", day.into()),
+        ("inputs", Value::Array(inputs)),
+        ("ok", ok.into()),
+    ])
+}
+
+/// The name an input is shown under: just its file name.
+fn file_name(file: &Path) -> String {
+    file.file_name().map_or_else(
+        || file.display().to_string(),
+        |name| name.to_string_lossy().into(),
+    )
+}
+
+fn print_inputs(results: &Checked) {
+    println!(
+        "{:<20}  {:>4}  {:<20}  {:>12}  Check",
+        "Input", "Part", "Answer", "Solve"
+    );
+    for (file, report, expected) in results {
+        let name = file_name(file);
+
+        let run = match &report.result {
+            Ok(run) => run,
+            Err(Failure::Parse(err)) => {
+                println!("{:<20}  {:>4}  couldn't parse input", name, "-");
+                println!("{}", err);
+                continue;
+            }
+            Err(err) => {
+                println!("{:<20}  {:>4}  {}", name, "-", err);
+                continue;
+            }
+        };
+
+        for part in &run.parts {
+            let status = status(&check(part, expected.get(&(report.day, part.number))));
+            println!(
+                "{:<20}  {:>4}  {:<20}  {:>12}  {}",
+                name,
+                part.number,
+                answer(part),
+                format!("{:.2?}", part.time),
+                status
+            );
+        }
+    }
+}
+
+/// Run each day and check its answers against the expected ones, returning
+/// whether everything that has an expected answer matched. A day whose input
+/// is a directory is checked against each input's own `.answers` file.
+pub fn verify(
+    days: &[Day],
+    source: &Source,
+    selection: Selection,
+    timeout: Option<Duration>,
+    answers: &Answers,
+) -> bool {
+    let mut ok = true;
+
+    for day in days {
+        if let Some(dir) = input::directory(day.number, source) {
+            match check_inputs(day, &dir, selection, timeout) {
+                Ok((results, passed)) => {
+                    for (file, report, expected) in &results {
+                        let label = format!("Day {} {}", day.number, file_name(file));
+                        print_verified(&label, report, expected);
+                    }
+                    ok &= passed;
+                }
+                Err(err) => {
+                    println!("Day {}: {}", day.number, err);
+                    ok = false;
+                }
+            }
+            continue;
+        }
+
+        let report = run(day, source, selection, timeout);
+        print_verified(&format!("Day {}", day.number), &report, answers);
+        // A missing default input just means that day hasn't been fetched
+        // yet, but one the user asked for has to be there
+        let unfetched = *source == Source::Default && report.missing_input();
+        ok &= passed(&report, answers) || unfetched;
+    }
+
+    ok
+}
+
+/// Print how each part of a report compares with the expected answers.
+fn print_verified(label: &str, report: &Report, answers: &Answers) {
+    let run = match &report.result {
+        Ok(run) => run,
+        Err(Failure::Input(err)) => {
+            println!("{}: {}", label, err);
+            return;
+        }
+        Err(err) => {
+            println!("{}: FAIL", label);
+            println!("{}", err);
+            return;
+        }
+    };
+
+    for part in &run.parts {
+        let status = match check(part, answers.get(&(report.day, part.number))) {
+            Check::Pass => "PASS".to_string(),
+            Check::Fail(expected) => {
+                format!("FAIL (expected {}, got {})", expected, answer(part))
+            }
+            Check::Missing => format!("MISSING (got {})", answer(part)),
+            Check::TimedOut => "FAIL (timed out)".to_string(),
+        };
+        println!("{} part {}: {}", label, part.number, status);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cancel::{Timeout, Token};
+    use crate::day1::Day1;
+    use crate::solution::Solution;
+    use crate::test_util::temp_dir;
+    use std::fs::write;
+
+    struct PanickingSolution;
+
+    impl Solution for PanickingSolution {
+        type Input = ();
+
+        fn parse(_input: &str) -> Result<Self::Input, ParseError> {
+            Ok(())
+        }
+
+        fn part1(_input: &Self::Input, _cancel: &Token) -> Result<Answer, Timeout> {
+            panic!("part 1 broke")
+        }
+
+        fn part2(_input: &Self::Input, _cancel: &Token) -> Result<Answer, Timeout> {
+            Ok(0u32.into())
+        }
+    }
+
+    #[test]
+    fn test_run_parallel_panic() {
+        let file = temp_dir("run-parallel").join("input.txt");
+        write(&file, "3 4\n4 3\n").unwrap();
+        let source = Source::Path(file.display().to_string());
+
+        let days = [Day::new::<Day1>(1), Day::new::<PanickingSolution>(2)];
+        let outcomes = run_parallel(&days, |day| {
+            Outcome::Single(run(day, &source, Selection::Both, None))
+        });
+
+        let Outcome::Single(report) = &outcomes[0] else {
+            panic!("day 1 wasn't run on a single input");
+        };
+        let answers: Vec<_> = match &report.result {
+            Ok(run) => run.parts.iter().map(answer).collect(),
+            Err(err) => panic!("day 1 failed: {}", err),
+        };
+        assert_eq!(
+            (report.day, answers),
+            (1, vec!["0".to_string(), "7".to_string()])
+        );
+
+        let Outcome::Single(report) = &outcomes[1] else {
+            panic!("day 2 wasn't run on a single input");
+        };
+        assert_eq!(report.day, 2);
+        assert!(
+            matches!(&report.result, Err(Failure::Panic(message)) if message == "part 1 broke")
+        );
+
+        assert!(outcomes[0].ok());
+        assert!(!outcomes.iter().all(Outcome::ok));
+
+        // Only the workers were quieted, so panics here still get printed
+        assert!(!QUIET.with(Cell::get));
+    }
+
+    #[test]
+    fn test_check_inputs() {
+        let day = Day::new::<Day1>(1);
+        let dir = temp_dir("check-inputs");
+        write(dir.join("alice.txt"), "3 4\n4 3\n").unwrap();
+        write(dir.join("alice.answers"), "1 1 0\n1 2 7\n").unwrap();
+        write(dir.join("bob.txt"), "1 1\n").unwrap();
+
+        let (results, ok) = check_inputs(&day, &dir, Selection::Both, None).unwrap();
+        assert_eq!(results.len(), 2);
+        assert!(ok);
+
+        write(dir.join("bob.answers"), "1 1 5\n").unwrap();
+        let (_, ok) = check_inputs(&day, &dir, Selection::Both, None).unwrap();
+        assert!(!ok);
+
+        let empty = temp_dir("check-inputs-empty");
+        assert!(check_inputs(&day, &empty, Selection::Both, None).is_err());
+    }
+
+    #[test]
+    fn test_verify_missing_input() {
+        let days = [Day::new::<Day1>(1)];
+        let missing = temp_dir("verify-missing").join("day1");
+        let source = Source::Path(missing.display().to_string());
+        assert!(!verify(
+            &days,
+            &source,
+            Selection::Both,
+            None,
+            &Answers::new()
+        ));
+    }
+
+    #[test]
+    fn test_missing_input_json() {
+        let report = Report {
+            day: 2,
+            result: Err(Failure::Input("Couldn't read data/day2".to_string())),
+        };
+        let json = |skip_missing| day_json(&report, skip_missing).to_string();
+
+        assert!(json(true).contains(r#""ok":true,"skipped":true"#));
+        assert!(json(false).contains(r#""ok":false,"skipped":false"#));
+        assert!(Outcome::Single(report).ok());
+    }
+
+    #[test]
+    fn test_panic_message() {
+        let payload = panic::catch_unwind(|| panic!("day {} broke", 6)).unwrap_err();
+        assert_eq!(panic_message(payload), "day 6 broke");
+
+        let payload = panic::catch_unwind(|| panic!("static")).unwrap_err();
+        assert_eq!(panic_message(payload), "static");
+
+        assert_eq!(panic_message(Box::new(42)), "unknown panic");
+    }
+
+    #[test]
+    fn test_check() {
+        let part = |answer| Part {
+            number: 1,
+            answer,
+            time: Duration::ZERO,
+        };
+        let expected = Answer::from(41usize);
+
+        assert_eq!(check(&part(Ok(41u32.into())), Some(&expected)), Check::Pass);
+        assert_eq!(
+            check(&part(Ok(40u32.into())), Some(&expected)),
+            Check::Fail(&expected)
+        );
+        assert_eq!(check(&part(Ok(40u32.into())), None), Check::Missing);
+        assert_eq!(
+            check(&part(Err(crate::cancel::Timeout)), Some(&expected)),
+            Check::TimedOut
+        );
+        assert!(Check::Missing.ok() && !Check::TimedOut.ok());
+    }
+}
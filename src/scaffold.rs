@@ -0,0 +1,185 @@
+use std::fs::{read_to_string, write};
+use std::path::Path;
+
+const TEMPLATE: &str = r#"use crate::answer::Answer;
+use crate::cancel::{Timeout, Token};
+use crate::error::ParseError;
+use crate::solution::Solution;
+
+pub struct DayN;
+
+/// TODO: give the puzzle input some structure
+#[derive(Clone, Debug, PartialEq)]
+pub struct Puzzle {
+    pub lines: Vec<String>,
+}
+
+impl Solution for DayN {
+    type Input = Puzzle;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input, _cancel: &Token) -> Result<Answer, Timeout> {
+        Ok(part1(input).into())
+    }
+
+    fn part2(input: &Self::Input, _cancel: &Token) -> Result<Answer, Timeout> {
+        Ok(part2(input).into())
+    }
+}
+
+pub fn part1(_input: &Puzzle) -> usize {
+    0
+}
+
+pub fn part2(_input: &Puzzle) -> usize {
+    0
+}
+
+pub fn parse(input: &str) -> Result<Puzzle, ParseError> {
+    Ok(Puzzle {
+        lines: input.lines().map(String::from).collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // TODO: paste the example from the puzzle description
+    const INPUT: &str = "\
+";
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(part1(&parse(INPUT).unwrap()), 0);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(part2(&parse(INPUT).unwrap()), 0);
+    }
+}
+"#;
+
+/// Generate `src/dayN.rs` from the template and register it in `lib.rs`.
+pub fn new_day(number: u32) -> Result<(), String> {
+    let src = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+    let module = src.join(format!("day{}.rs", number));
+    let lib = src.join("lib.rs");
+
+    if module.exists() {
+        return Err(format!("{} already exists", module.display()));
+    }
+
+    let registered = read_to_string(&lib)
+        .map_err(|err| format!("Couldn't read {}: {}", lib.display(), err))
+        .and_then(|contents| register(&contents, number))?;
+
+    write(&module, render(number))
+        .map_err(|err| format!("Couldn't write {}: {}", module.display(), err))?;
+    write(&lib, registered).map_err(|err| format!("Couldn't update {}: {}", lib.display(), err))?;
+
+    println!("Created {} and registered day {}", module.display(), number);
+    Ok(())
+}
+
+fn render(number: u32) -> String {
+    TEMPLATE.replace("DayN", &format!("Day{}", number))
+}
+
+/// Add the `mod` declaration and registry entry for a day to `lib.rs`,
+/// keeping both lists in order.
+fn register(lib: &str, number: u32) -> Result<String, String> {
+    let mut lines: Vec<String> = lib.lines().map(String::from).collect();
+
+    let entry = format!("    Day::new::<day{0}::Day{0}>({0}),", number);
+    insert_sorted(&mut lines, "    Day::new::<day", entry, number)
+        .ok_or("Couldn't find the DAYS registry in lib.rs")?;
+
+    let declaration = format!("pub mod day{};", number);
+    insert_sorted(&mut lines, "pub mod day", declaration, number)
+        .ok_or("Couldn't find the day modules in lib.rs")?;
+
+    let mut registered = lines.join("\n");
+    registered.push('\n');
+    Ok(registered)
+}
+
+/// Insert `line` among the run of lines starting with `prefix`, before the
+/// first one for a later day.
+fn insert_sorted(lines: &mut Vec<String>, prefix: &str, line: String, number: u32) -> Option<()> {
+    let matching: Vec<usize> = (0..lines.len())
+        .filter(|&i| lines[i].starts_with(prefix))
+        .collect();
+    let last = *matching.last()?;
+
+    let later = matching.into_iter().find(|&i| {
+        let digits: String = lines[i][prefix.len()..]
+            .chars()
+            .take_while(|c| c.is_ascii_digit())
+            .collect();
+        digits.parse::<u32>().is_ok_and(|day| day > number)
+    });
+
+    lines.insert(later.unwrap_or(last + 1), line);
+    Some(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIB: &str = "\
+pub mod bench;
+pub mod day1;
+pub mod day10;
+pub mod error;
+
+pub const DAYS: &[Day] = &[
+    Day::new::<day1::Day1>(1),
+    Day::new::<day10::Day10>(10),
+];
+";
+
+    #[test]
+    fn test_register() {
+        assert_eq!(
+            register(LIB, 8).unwrap(),
+            "\
+pub mod bench;
+pub mod day1;
+pub mod day8;
+pub mod day10;
+pub mod error;
+
+pub const DAYS: &[Day] = &[
+    Day::new::<day1::Day1>(1),
+    Day::new::<day8::Day8>(8),
+    Day::new::<day10::Day10>(10),
+];
+"
+        );
+    }
+
+    #[test]
+    fn test_register_last() {
+        let registered = register(LIB, 12).unwrap();
+        assert!(registered.contains("pub mod day10;\npub mod day12;\npub mod error;"));
+        assert!(registered.contains("(10),\n    Day::new::<day12::Day12>(12),\n];"));
+    }
+
+    #[test]
+    fn test_register_missing_registry() {
+        assert!(register("pub mod day1;\n", 2).is_err());
+    }
+
+    #[test]
+    fn test_render() {
+        let module = render(9);
+        assert!(module.contains("pub struct Day9;"));
+        assert!(module.contains("impl Solution for Day9 {"));
+    }
+}
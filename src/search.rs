@@ -0,0 +1,354 @@
+use std::cmp::{Ordering, Reverse};
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+/// What a search found out. Costs and predecessors are known for every
+/// state it settled, which is everything reachable when there's no goal, or
+/// everything at most as far as the goal when there is one.
+#[derive(Clone, Debug)]
+pub struct Search<S> {
+    states: Vec<S>,
+    ids: HashMap<S, usize>,
+    costs: Vec<u64>,
+    settled: Vec<bool>,
+    /// Every state that a cheapest path to this one comes straight from
+    predecessors: Vec<Vec<usize>>,
+    goal: Option<usize>,
+}
+
+impl<S: Clone + Eq + Hash> Search<S> {
+    fn new(start: S) -> Self {
+        Search {
+            states: vec![start.clone()],
+            ids: HashMap::from([(start, 0)]),
+            costs: vec![0],
+            settled: vec![false],
+            predecessors: vec![vec![]],
+            goal: None,
+        }
+    }
+
+    /// The id of `state`, adding it as unreached if it's new.
+    fn id(&mut self, state: S) -> usize {
+        match self.ids.entry(state) {
+            Entry::Occupied(entry) => *entry.get(),
+            Entry::Vacant(entry) => {
+                let id = self.states.len();
+                self.states.push(entry.key().clone());
+                entry.insert(id);
+                self.costs.push(u64::MAX);
+                self.settled.push(false);
+                self.predecessors.push(vec![]);
+                id
+            }
+        }
+    }
+
+    /// Record a way to reach `next` from `from` at `cost`, returning whether
+    /// it's the cheapest so far. A settled state keeps its predecessors, or a
+    /// zero-cost step back to it would make them go round in a loop.
+    fn relax(&mut self, from: usize, next: usize, cost: u64) -> bool {
+        match cost.cmp(&self.costs[next]) {
+            Ordering::Less => {
+                self.costs[next] = cost;
+                self.predecessors[next] = vec![from];
+                true
+            }
+            Ordering::Equal if !self.settled[next] => {
+                self.predecessors[next].push(from);
+                false
+            }
+            Ordering::Equal => false,
+            Ordering::Greater => false,
+        }
+    }
+
+    fn settled_id(&self, state: &S) -> Option<usize> {
+        self.ids.get(state).copied().filter(|&id| self.settled[id])
+    }
+
+    /// The first goal state the search reached, if it reached one.
+    pub fn goal(&self) -> Option<&S> {
+        self.goal.map(|id| &self.states[id])
+    }
+
+    /// The cost of the cheapest path to the goal.
+    pub fn distance(&self) -> Option<u64> {
+        self.goal.map(|id| self.costs[id])
+    }
+
+    /// A cheapest path from the start to the goal, both included.
+    pub fn path(&self) -> Option<Vec<S>> {
+        self.goal.map(|id| self.path_from_id(id))
+    }
+
+    /// The cost of the cheapest path to `state`.
+    pub fn cost(&self, state: &S) -> Option<u64> {
+        self.settled_id(state).map(|id| self.costs[id])
+    }
+
+    /// A cheapest path from the start to `state`, both included.
+    pub fn path_to(&self, state: &S) -> Option<Vec<S>> {
+        self.settled_id(state).map(|id| self.path_from_id(id))
+    }
+
+    fn path_from_id(&self, mut id: usize) -> Vec<S> {
+        let mut path = vec![self.states[id].clone()];
+        while let Some(&previous) = self.predecessors[id].first() {
+            path.push(self.states[previous].clone());
+            id = previous;
+        }
+        path.reverse();
+        path
+    }
+
+    /// Every state that a cheapest path to `state` comes straight from, when
+    /// there's more than one equally short way there.
+    pub fn predecessors(&self, state: &S) -> Vec<&S> {
+        match self.settled_id(state) {
+            Some(id) => self.predecessors[id]
+                .iter()
+                .map(|&previous| &self.states[previous])
+                .collect(),
+            None => vec![],
+        }
+    }
+
+    /// Every state on any of the cheapest paths to the goal.
+    pub fn shortest_path_states(&self) -> HashSet<&S> {
+        let mut seen = HashSet::new();
+        let mut stack: Vec<usize> = self.goal.into_iter().collect();
+        while let Some(id) = stack.pop() {
+            if seen.insert(id) {
+                stack.extend(&self.predecessors[id]);
+            }
+        }
+        seen.into_iter().map(|id| &self.states[id]).collect()
+    }
+}
+
+/// Breadth-first search where every step costs one. Stops once everything
+/// as close as the first state where `is_goal` holds has been seen; pass
+/// `|_| false` to search everything reachable.
+pub fn bfs<S, I>(
+    start: S,
+    mut neighbours: impl FnMut(&S) -> I,
+    is_goal: impl Fn(&S) -> bool,
+) -> Search<S>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut search = Search::new(start);
+    let mut queue = VecDeque::from([0]);
+
+    while let Some(id) = queue.pop_front() {
+        let cost = search.costs[id];
+        if search.distance().is_some_and(|distance| cost > distance) {
+            break;
+        }
+        search.settled[id] = true;
+
+        let state = search.states[id].clone();
+        if is_goal(&state) && search.goal.is_none() {
+            search.goal = Some(id);
+        }
+        if search.goal.is_some() {
+            continue; // Nothing past the goal can be on a shortest path
+        }
+
+        for next in neighbours(&state) {
+            let next = search.id(next);
+            if search.relax(id, next, cost + 1) {
+                queue.push_back(next);
+            }
+        }
+    }
+
+    search
+}
+
+/// Dijkstra's algorithm, where `neighbours` gives each next state with the
+/// cost of moving to it.
+pub fn dijkstra<S, I>(
+    start: S,
+    neighbours: impl FnMut(&S) -> I,
+    is_goal: impl Fn(&S) -> bool,
+) -> Search<S>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, u64)>,
+{
+    astar(start, neighbours, |_| 0, is_goal)
+}
+
+/// A* search, guided towards the goal by `heuristic`. To find the cheapest
+/// path it has to never overestimate the remaining cost, like the Manhattan
+/// distance does for a grid without diagonal moves.
+pub fn astar<S, I>(
+    start: S,
+    mut neighbours: impl FnMut(&S) -> I,
+    heuristic: impl Fn(&S) -> u64,
+    is_goal: impl Fn(&S) -> bool,
+) -> Search<S>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, u64)>,
+{
+    let mut queue = BinaryHeap::from([Reverse((heuristic(&start), 0, 0))]);
+    let mut search = Search::new(start);
+
+    while let Some(Reverse((estimate, cost, id))) = queue.pop() {
+        if search
+            .distance()
+            .is_some_and(|distance| estimate > distance)
+        {
+            break;
+        }
+        if cost > search.costs[id] {
+            continue; // Already found a cheaper way here
+        }
+        search.settled[id] = true;
+
+        let state = search.states[id].clone();
+        if is_goal(&state) && search.goal.is_none() {
+            search.goal = Some(id);
+        }
+        if search.goal == Some(id) {
+            continue;
+        }
+
+        for (next, step) in neighbours(&state) {
+            let next = search.id(next);
+            let next_cost = cost + step;
+            if search.relax(id, next, next_cost) {
+                let estimate = next_cost + heuristic(&search.states[next]);
+                queue.push(Reverse((estimate, next_cost, next)));
+            }
+        }
+    }
+
+    search
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Grid;
+    use crate::point::{Dir4, Point};
+
+    const MAZE: &str = "\
+#######
+#S...##
+#.#.#.#
+#...#E#
+###...#
+#######";
+
+    fn maze() -> (Grid<char>, (usize, usize), (usize, usize)) {
+        let grid = Grid::parse(0, MAZE, |_, c| Ok(c)).unwrap();
+        let start = grid.position(|&c| c == 'S').unwrap();
+        let end = grid.position(|&c| c == 'E').unwrap();
+        (grid, start, end)
+    }
+
+    #[test]
+    fn test_bfs() {
+        let (grid, start, end) = maze();
+        let open = |&position: &(usize, usize)| {
+            grid.neighbours4(position)
+                .filter(|&next| grid[next] != '#')
+                .collect::<Vec<_>>()
+        };
+
+        let search = bfs(start, open, |&position| position == end);
+        assert_eq!(search.distance(), Some(8));
+        let path = search.path().unwrap();
+        assert_eq!((path[0], path[8]), (start, end));
+        assert!(path
+            .windows(2)
+            .all(|step| { Point::from(step[0]).manhattan(Point::from(step[1])) == 1 }));
+
+        // Around either side of the wall in the middle
+        assert_eq!(search.predecessors(&(3, 3)), [&(3, 2), &(2, 3)]);
+        assert_eq!(search.shortest_path_states().len(), 12);
+
+        let everything = bfs(start, open, |_| false);
+        assert_eq!(everything.goal(), None);
+        assert_eq!(everything.cost(&end), Some(8));
+        assert_eq!(everything.cost(&(4, 1)), Some(3));
+        assert_eq!(everything.cost(&(5, 2)), Some(9));
+        assert_eq!(everything.cost(&(0, 0)), None);
+    }
+
+    #[test]
+    fn test_dijkstra_with_directions() {
+        // Moving forward costs 1 and turning costs 1000, like a reindeer race
+        let (grid, start, end) = maze();
+        let moves = |&(position, facing): &((usize, usize), Dir4)| {
+            let mut moves = vec![
+                ((position, facing.turn_left()), 1000),
+                ((position, facing.turn_right()), 1000),
+            ];
+            if let Some(ahead) = grid.offset(position, facing) {
+                if grid[ahead] != '#' {
+                    moves.push(((ahead, facing), 1));
+                }
+            }
+            moves
+        };
+
+        let search = dijkstra((start, Dir4::Right), moves, |&(position, _)| {
+            position == end
+        });
+        assert_eq!(search.distance(), Some(3008));
+        assert_eq!(search.goal(), Some(&(end, Dir4::Up)));
+        assert_eq!(search.path().unwrap().len(), 12);
+
+        // Going down first takes two more turns, so only one way is cheapest
+        let tiles: HashSet<_> = search
+            .shortest_path_states()
+            .into_iter()
+            .map(|&(position, _)| position)
+            .collect();
+        assert_eq!(tiles.len(), 9);
+    }
+
+    #[test]
+    fn test_astar() {
+        let (grid, start, end) = maze();
+        let open = |&position: &(usize, usize)| {
+            grid.neighbours4(position)
+                .filter(|&next| grid[next] != '#')
+                .map(|next| (next, 1))
+                .collect::<Vec<_>>()
+        };
+        let heuristic =
+            |&position: &(usize, usize)| Point::from(position).manhattan(Point::from(end));
+
+        let search = astar(start, open, heuristic, |&position| position == end);
+        assert_eq!(search.distance(), Some(8));
+        assert_eq!(search.path().unwrap().last(), Some(&end));
+        // Nothing past the goal gets settled
+        assert_eq!(search.cost(&(5, 2)), None);
+
+        let nowhere = astar(start, open, heuristic, |&position| position == (0, 0));
+        assert_eq!(nowhere.distance(), None);
+        assert_eq!(nowhere.path(), None);
+    }
+
+    #[test]
+    fn test_zero_cost_steps() {
+        let free = |&n: &u32| match n {
+            0 => vec![(1, 0)],
+            1 => vec![(0, 0), (2, 1)],
+            _ => vec![],
+        };
+
+        let search = dijkstra(0, free, |&n| n == 2);
+        assert_eq!(search.path(), Some(vec![0, 1, 2]));
+        assert_eq!(search.predecessors(&0), Vec::<&u32>::new());
+        assert_eq!(search.shortest_path_states().len(), 3);
+    }
+}
@@ -0,0 +1,157 @@
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use crate::answer::Answer;
+use crate::cancel::{Timeout, Token};
+use crate::error::ParseError;
+
+/// A single day's puzzle. Parsing is kept apart from the two parts so the
+/// runner can drive every day the same way. Parts that can run for a long
+/// time should check the token now and then and give up once it runs out.
+pub trait Solution {
+    type Input;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+    fn part1(input: &Self::Input, cancel: &Token) -> Result<Answer, Timeout>;
+    fn part2(input: &Self::Input, cancel: &Token) -> Result<Answer, Timeout>;
+}
+
+/// Which parts of a day to run.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Selection {
+    #[default]
+    Both,
+    Only(usize),
+}
+
+impl Selection {
+    pub fn includes(self, part: usize) -> bool {
+        match self {
+            Selection::Both => true,
+            Selection::Only(only) => only == part,
+        }
+    }
+}
+
+/// The answer to one part along with how long it took to compute.
+pub struct Part {
+    pub number: usize,
+    pub answer: Result<Answer, Timeout>,
+    pub time: Duration,
+}
+
+/// The result of running a day: how long parsing took and each selected part.
+pub struct Run {
+    pub parse: Duration,
+    pub parts: Vec<Part>,
+}
+
+impl Run {
+    pub fn timed_out(&self) -> bool {
+        self.parts.iter().any(|part| part.answer.is_err())
+    }
+}
+
+/// Timings of repeated runs of each selected part, keyed by part number.
+pub type Samples = Vec<(usize, Vec<Duration>)>;
+
+/// An entry in the registry of implemented days.
+pub struct Day {
+    pub number: u32,
+    solve: fn(&str, Selection, Option<Duration>) -> Result<Run, ParseError>,
+    sample: fn(&str, usize, Selection) -> Result<Samples, ParseError>,
+}
+
+impl Day {
+    pub const fn new<S: Solution>(number: u32) -> Self {
+        Day {
+            number,
+            solve: solve::<S>,
+            sample: sample::<S>,
+        }
+    }
+
+    /// Parse the input and run the selected parts, timing each step
+    /// separately. Parts that aren't selected are never computed, and each
+    /// part that is gets `timeout` to finish in.
+    pub fn solve(
+        &self,
+        input: &str,
+        selection: Selection,
+        timeout: Option<Duration>,
+    ) -> Result<Run, ParseError> {
+        (self.solve)(input, selection, timeout)
+    }
+
+    /// Parse the input once, then time each selected part `runs` times.
+    pub fn sample(
+        &self,
+        input: &str,
+        runs: usize,
+        selection: Selection,
+    ) -> Result<Samples, ParseError> {
+        (self.sample)(input, runs, selection)
+    }
+}
+
+type Solver<S> = fn(&<S as Solution>::Input, &Token) -> Result<Answer, Timeout>;
+
+/// The selected parts of a solution, paired with their numbers.
+fn parts<S: Solution>(selection: Selection) -> Vec<(usize, Solver<S>)> {
+    [(1, S::part1 as fn(&_, &_) -> _), (2, S::part2)]
+        .into_iter()
+        .filter(|&(number, _)| selection.includes(number))
+        .collect()
+}
+
+fn solve<S: Solution>(
+    input: &str,
+    selection: Selection,
+    timeout: Option<Duration>,
+) -> Result<Run, ParseError> {
+    let (parsed, parse) = timed(|| S::parse(input));
+    let parsed = parsed?;
+    Ok(Run {
+        parse,
+        parts: parts::<S>(selection)
+            .into_iter()
+            .map(|(number, solve)| {
+                let cancel = Token::after(timeout);
+                let (answer, time) = timed(|| solve(&parsed, &cancel));
+                Part {
+                    number,
+                    answer,
+                    time,
+                }
+            })
+            .collect(),
+    })
+}
+
+fn sample<S: Solution>(
+    input: &str,
+    runs: usize,
+    selection: Selection,
+) -> Result<Samples, ParseError> {
+    let parsed = S::parse(input)?;
+    Ok(parts::<S>(selection)
+        .into_iter()
+        .map(|(number, solve)| {
+            let times = (0..runs)
+                .map(|_| timed(|| black_box(solve(&parsed, &Token::never()))).1)
+                .collect();
+            (number, times)
+        })
+        .collect())
+}
+
+fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let value = f();
+    (value, start.elapsed())
+}
+
+/// Look up a day in the registry.
+pub fn find(days: &[Day], number: u32) -> Option<&Day> {
+    days.iter().find(|day| day.number == number)
+}
@@ -0,0 +1,382 @@
+use std::fs::{read_to_string, write};
+use std::io;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::config;
+
+/// Where past submissions are remembered. Each year gets its own file, since
+/// the same day and part means a different puzzle in another year.
+pub fn history_path() -> String {
+    let config = config::get();
+    config.path(&format!("submissions-{}", config.year))
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+}
+
+/// What the site said about a submitted answer.
+#[derive(Debug, PartialEq)]
+pub enum Outcome {
+    Right,
+    /// A wrong answer, maybe with a hint, and how long until the next try
+    Wrong(Option<Hint>, Duration),
+    /// Submitted too soon after the last answer, with the time left to wait
+    TooSoon(Duration),
+    /// The part has already been solved
+    AlreadySolved,
+    Unknown(String),
+}
+
+/// Work out the outcome from the page the site sends back.
+pub fn parse_response(page: &str) -> Outcome {
+    let text = article(page);
+
+    if text.contains("That's the right answer") {
+        Outcome::Right
+    } else if text.contains("That's not the right answer") {
+        let hint = if text.contains("too high") {
+            Some(Hint::TooHigh)
+        } else if text.contains("too low") {
+            Some(Hint::TooLow)
+        } else {
+            None
+        };
+        let wait = text
+            .split("wait ")
+            .nth(1)
+            .and_then(|rest| rest.split(" before").next())
+            .map_or(Duration::from_secs(60), parse_words);
+        Outcome::Wrong(hint, wait)
+    } else if text.contains("You gave an answer too recently") {
+        let wait = text
+            .split("You have ")
+            .nth(1)
+            .and_then(|rest| rest.split(" left to wait").next())
+            .map_or(Duration::from_secs(60), parse_wait);
+        Outcome::TooSoon(wait)
+    } else if text.contains("You don't seem to be solving the right level") {
+        Outcome::AlreadySolved
+    } else {
+        Outcome::Unknown(text.trim().to_string())
+    }
+}
+
+/// The text of the page's `<article>`, with the tags stripped out.
+fn article(page: &str) -> String {
+    let start = page.find("<article").unwrap_or(0);
+    let end = page[start..]
+        .find("</article>")
+        .map_or(page.len(), |end| start + end);
+
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in page[start..end].chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text
+}
+
+/// Parse a wait like `1m 5s` or `34s`.
+fn parse_wait(wait: &str) -> Duration {
+    let mut seconds = 0;
+    for token in wait.split_whitespace() {
+        // The unit is the last character, whatever the server put there
+        let Some((end, unit)) = token.char_indices().last() else {
+            continue;
+        };
+        let number: u64 = token[..end].parse().unwrap_or(0);
+        seconds += match unit {
+            'h' => number * 3600,
+            'm' => number * 60,
+            _ => number,
+        };
+    }
+    Duration::from_secs(seconds)
+}
+
+/// Parse a wait like `one minute` or `5 minutes`.
+fn parse_words(wait: &str) -> Duration {
+    let mut words = wait.split_whitespace();
+    let number = match words.next() {
+        Some("one") | Some("a") => 1,
+        Some(number) => number.parse().unwrap_or(1),
+        None => 1,
+    };
+    match words.next() {
+        Some(unit) if unit.starts_with("second") => Duration::from_secs(number),
+        _ => Duration::from_secs(number * 60),
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Verdict {
+    Right,
+    Wrong(Option<Hint>),
+}
+
+#[derive(Debug, PartialEq)]
+struct Entry {
+    day: u32,
+    part: usize,
+    answer: String,
+    verdict: Verdict,
+}
+
+/// Every answer submitted so far, and when the site will take the next one.
+#[derive(Debug, Default, PartialEq)]
+pub struct History {
+    entries: Vec<Entry>,
+    /// Unix time before which nothing should be submitted
+    cooldown: u64,
+}
+
+impl History {
+    pub fn load(path: &str) -> io::Result<Self> {
+        match read_to_string(path) {
+            Ok(contents) => Ok(History::parse(&contents)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(History::default()),
+            Err(err) => Err(err),
+        }
+    }
+
+    pub fn save(&self, path: &str) -> io::Result<()> {
+        write(path, self.to_string())
+    }
+
+    /// Lines are `<day> <part> <right|wrong|high|low> <answer>`, plus a
+    /// `cooldown <unix time>` line.
+    fn parse(contents: &str) -> Self {
+        let mut history = History::default();
+
+        for line in contents.lines() {
+            let fields: Vec<&str> = line.splitn(4, ' ').collect();
+            match fields[..] {
+                ["cooldown", until] => history.cooldown = until.parse().unwrap_or(0),
+                [day, part, outcome, answer] => {
+                    let verdict = match outcome {
+                        "right" => Verdict::Right,
+                        "wrong" => Verdict::Wrong(None),
+                        "high" => Verdict::Wrong(Some(Hint::TooHigh)),
+                        "low" => Verdict::Wrong(Some(Hint::TooLow)),
+                        _ => continue,
+                    };
+                    if let (Ok(day), Ok(part)) = (day.parse(), part.parse()) {
+                        history.entries.push(Entry {
+                            day,
+                            part,
+                            answer: answer.to_string(),
+                            verdict,
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        history
+    }
+
+    /// Check whether an answer is worth submitting at `now` (Unix seconds),
+    /// explaining why not if it isn't.
+    pub fn check(&self, day: u32, part: usize, answer: &str, now: u64) -> Result<(), String> {
+        if now < self.cooldown {
+            return Err(format!(
+                "Still cooling down, try again in {}s.",
+                self.cooldown - now
+            ));
+        }
+
+        let number = answer.parse::<i128>().ok();
+        for entry in self.entries(day, part) {
+            let Verdict::Wrong(hint) = entry.verdict else {
+                return Err(format!("Already solved with {}.", entry.answer));
+            };
+            if entry.answer == answer {
+                return Err(format!("{} was already submitted and was wrong.", answer));
+            }
+
+            let (Some(number), Ok(wrong)) = (number, entry.answer.parse::<i128>()) else {
+                continue;
+            };
+            match hint {
+                Some(Hint::TooHigh) if number >= wrong => {
+                    return Err(format!("{} was too high, so {} is too.", wrong, number));
+                }
+                Some(Hint::TooLow) if number <= wrong => {
+                    return Err(format!("{} was too low, so {} is too.", wrong, number));
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Remember the outcome of submitting an answer at `now`.
+    pub fn record(&mut self, day: u32, part: usize, answer: &str, outcome: &Outcome, now: u64) {
+        let verdict = match *outcome {
+            Outcome::Right => Verdict::Right,
+            Outcome::Wrong(hint, wait) => {
+                self.cooldown = now + wait.as_secs();
+                Verdict::Wrong(hint)
+            }
+            Outcome::TooSoon(wait) => {
+                self.cooldown = now + wait.as_secs();
+                return;
+            }
+            Outcome::AlreadySolved | Outcome::Unknown(_) => return,
+        };
+
+        self.entries.push(Entry {
+            day,
+            part,
+            answer: answer.to_string(),
+            verdict,
+        });
+    }
+
+    fn entries(&self, day: u32, part: usize) -> impl Iterator<Item = &Entry> {
+        self.entries
+            .iter()
+            .filter(move |entry| entry.day == day && entry.part == part)
+    }
+}
+
+impl std::fmt::Display for History {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        writeln!(f, "cooldown {}", self.cooldown)?;
+        for entry in &self.entries {
+            let outcome = match entry.verdict {
+                Verdict::Right => "right",
+                Verdict::Wrong(None) => "wrong",
+                Verdict::Wrong(Some(Hint::TooHigh)) => "high",
+                Verdict::Wrong(Some(Hint::TooLow)) => "low",
+            };
+            writeln!(
+                f,
+                "{} {} {} {}",
+                entry.day, entry.part, outcome, entry.answer
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Seconds since the Unix epoch.
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |since| since.as_secs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(text: &str) -> String {
+        format!(
+            "<html><main><article><p>{}</p></article></main></html>",
+            text
+        )
+    }
+
+    #[test]
+    fn test_parse_right() {
+        let response = page("That's the right answer!  You are <em>one gold star</em> closer.");
+        assert_eq!(parse_response(&response), Outcome::Right);
+    }
+
+    #[test]
+    fn test_parse_wrong() {
+        let response = page(
+            "That's not the right answer; your answer is too high.  If you're stuck, \
+             make sure you're using the full input data. Please wait one minute before \
+             trying again. <a href=\"/2024/day/1\">[Return to Day 1]</a>",
+        );
+        assert_eq!(
+            parse_response(&response),
+            Outcome::Wrong(Some(Hint::TooHigh), Duration::from_secs(60))
+        );
+
+        let response = page(
+            "That's not the right answer.  Because you have guessed incorrectly 4 times \
+             on this puzzle, please wait 5 minutes before trying again.",
+        );
+        assert_eq!(
+            parse_response(&response),
+            Outcome::Wrong(None, Duration::from_secs(300))
+        );
+    }
+
+    #[test]
+    fn test_parse_too_soon() {
+        let response = page(
+            "You gave an answer too recently; you have to wait after submitting an \
+             answer before trying again.  You have 1m 5s left to wait.",
+        );
+        assert_eq!(
+            parse_response(&response),
+            Outcome::TooSoon(Duration::from_secs(65))
+        );
+    }
+
+    #[test]
+    fn test_parse_wait() {
+        assert_eq!(parse_wait("1h 2m 5s"), Duration::from_secs(3725));
+        assert_eq!(parse_wait("5秒 3m"), Duration::from_secs(185));
+        assert_eq!(parse_wait("é"), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_parse_solved() {
+        let response =
+            page("You don't seem to be solving the right level.  Did you already complete it?");
+        assert_eq!(parse_response(&response), Outcome::AlreadySolved);
+    }
+
+    #[test]
+    fn test_history_checks() {
+        let mut history = History::default();
+        let wrong = |hint| Outcome::Wrong(hint, Duration::from_secs(60));
+        history.record(1, 1, "500", &wrong(Some(Hint::TooHigh)), 1000);
+        history.record(1, 1, "10", &wrong(Some(Hint::TooLow)), 1100);
+        history.record(1, 1, "abc", &wrong(None), 1200);
+
+        assert!(history.check(1, 1, "42", 1200).is_err()); // Still cooling down
+        assert!(history.check(1, 1, "42", 1260).is_ok());
+        assert!(history.check(1, 1, "abc", 1260).is_err());
+        assert!(history.check(1, 1, "500", 1260).is_err());
+        assert!(history.check(1, 1, "600", 1260).is_err());
+        assert!(history.check(1, 1, "5", 1260).is_err());
+        assert!(history.check(1, 2, "600", 1260).is_ok());
+
+        history.record(1, 1, "42", &Outcome::Right, 1300);
+        assert!(history.check(1, 1, "43", 1300).is_err());
+    }
+
+    #[test]
+    fn test_history_round_trip() {
+        let mut history = History::default();
+        history.record(3, 2, "48", &Outcome::Right, 0);
+        history.record(
+            6,
+            1,
+            "1 2",
+            &Outcome::Wrong(Some(Hint::TooLow), Duration::from_secs(60)),
+            100,
+        );
+        history.record(6, 1, "7", &Outcome::TooSoon(Duration::from_secs(30)), 200);
+
+        let contents = history.to_string();
+        assert_eq!(contents, "cooldown 230\n3 2 right 48\n6 1 low 1 2\n");
+        assert_eq!(History::parse(&contents), history);
+    }
+}
@@ -0,0 +1,13 @@
+//! Helpers shared by the unit tests.
+
+use std::env;
+use std::fs::{create_dir_all, remove_dir_all};
+use std::path::PathBuf;
+
+/// A fresh, empty directory for a test, unique to `name` and this process.
+pub fn temp_dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("aoc_2024-{}-{}", name, std::process::id()));
+    let _ = remove_dir_all(&dir);
+    create_dir_all(&dir).unwrap();
+    dir
+}
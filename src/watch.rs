@@ -0,0 +1,183 @@
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use crate::input::{self, Source};
+use crate::runner::{self, Format};
+use crate::solution::{Day, Selection};
+
+/// How often to check whether anything changed.
+const POLL: Duration = Duration::from_millis(250);
+
+/// What a file looked like last time we checked, or `None` if it was missing.
+type Stamp = Option<(SystemTime, u64)>;
+
+/// The example inputs saved for a day next to its input, as `dayN.example`,
+/// `dayN.example2` and so on.
+fn examples(input: &Path, day: u32) -> Vec<PathBuf> {
+    let dir = input.parent().unwrap_or(Path::new("."));
+    let prefix = format!("day{}.example", day);
+
+    let Ok(entries) = fs::read_dir(dir) else {
+        return vec![];
+    };
+    let mut examples: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with(&prefix))
+        })
+        .collect();
+    examples.sort();
+    examples
+}
+
+fn stamp(path: &Path) -> Stamp {
+    let metadata = fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+/// Every file being watched along with its stamp: the input, or every input
+/// and answer file when it's a directory, and the examples. Directories and
+/// examples are looked up again each time so new files get picked up.
+fn snapshot(input: &Path, day: u32) -> Vec<(PathBuf, Stamp)> {
+    let mut paths = vec![input.to_path_buf()];
+    if input.is_dir() {
+        for file in input::files(input).unwrap_or_default() {
+            paths.push(input::answers_path(&file));
+            paths.push(file);
+        }
+    }
+    paths.extend(examples(input, day));
+    paths
+        .into_iter()
+        .map(|path| {
+            let stamp = stamp(&path);
+            (path, stamp)
+        })
+        .collect()
+}
+
+/// Rerun a day whenever its input or one of its examples changes, until the
+/// process is killed. A directory of inputs is run and checked like a plain
+/// `<day>` run would. Solvers are run on the input read fresh from disk,
+/// even when it was embedded into the binary.
+pub fn watch(
+    day: &Day,
+    input: &Path,
+    selection: Selection,
+    timeout: Option<Duration>,
+    format: Format,
+) -> ! {
+    let mut last = vec![];
+
+    loop {
+        let current = snapshot(input, day.number);
+        if current != last {
+            redraw(day, input, selection, timeout, format);
+            last = current;
+        }
+        thread::sleep(POLL);
+    }
+}
+
+fn redraw(
+    day: &Day,
+    input: &Path,
+    selection: Selection,
+    timeout: Option<Duration>,
+    format: Format,
+) {
+    // Clear the screen and move the cursor back to the top
+    print!("\x1b[2J\x1b[H");
+    println!("Watching day {}, press Ctrl-C to stop", day.number);
+
+    let mut paths = vec![input.to_path_buf()];
+    paths.extend(examples(input, day.number));
+    for path in paths {
+        println!();
+        println!("== {} ==", path.display());
+        if path.is_dir() {
+            runner::run_inputs(day, &path, selection, timeout, format);
+        } else {
+            let source = Source::Path(path.display().to_string());
+            runner::run_day(day, &source, selection, timeout, format);
+        }
+    }
+
+    let _ = io::stdout().flush();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_dir;
+    use std::fs::{create_dir_all, write};
+
+    #[test]
+    fn test_examples() {
+        let dir = temp_dir("examples");
+        for name in [
+            "day1",
+            "day1.example2",
+            "day1.example",
+            "day12.example",
+            "day2",
+        ] {
+            write(dir.join(name), "").unwrap();
+        }
+
+        assert_eq!(
+            examples(&dir.join("day1"), 1),
+            vec![dir.join("day1.example"), dir.join("day1.example2")]
+        );
+        assert!(examples(&dir.join("day3"), 3).is_empty());
+    }
+
+    #[test]
+    fn test_snapshot_changes() {
+        let dir = temp_dir("snapshot");
+        let input = dir.join("day4");
+
+        let missing = snapshot(&input, 4);
+        assert_eq!(missing, vec![(input.clone(), None)]);
+
+        write(&input, "XMAS").unwrap();
+        let created = snapshot(&input, 4);
+        assert_ne!(created, missing);
+        assert_eq!(snapshot(&input, 4), created);
+
+        write(dir.join("day4.example"), "MMSS").unwrap();
+        let example = snapshot(&input, 4);
+        assert_eq!(example.len(), 2);
+
+        write(&input, "XMASXMAS").unwrap();
+        assert_ne!(snapshot(&input, 4), example);
+    }
+
+    #[test]
+    fn test_snapshot_directory() {
+        let dir = temp_dir("snapshot-dir");
+        let inputs = dir.join("day6");
+        create_dir_all(&inputs).unwrap();
+        write(inputs.join("alice"), "^").unwrap();
+
+        let before = snapshot(&inputs, 6);
+        let paths: Vec<_> = before.iter().map(|(path, _)| path.clone()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                inputs.clone(),
+                inputs.join("alice.answers"),
+                inputs.join("alice")
+            ]
+        );
+
+        write(inputs.join("alice.answers"), "1 41").unwrap();
+        assert_ne!(snapshot(&inputs, 6), before);
+    }
+}